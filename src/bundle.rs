@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ffi::OsStr,
     fmt,
     path::{Path, PathBuf},
@@ -14,6 +14,32 @@ use std::fs;
 #[cfg(not(test))]
 use deno_core::serde_json::Value;
 #[cfg(not(test))]
+use lightningcss::css_modules::{Config as CssModulesConfig, Pattern};
+#[cfg(not(test))]
+use notify::{RecursiveMode, Watcher};
+#[cfg(not(test))]
+use lightningcss::stylesheet::{MinifyOptions, ParserOptions, PrinterOptions, StyleSheet};
+#[cfg(not(test))]
+use lightningcss::rules::CssRule;
+#[cfg(not(test))]
+use lightningcss::targets::Targets;
+#[cfg(not(test))]
+use lightningcss::traits::ToCss;
+#[cfg(not(test))]
+use oxc_allocator::Allocator;
+#[cfg(not(test))]
+use oxc_ast::ast::{Expression, ImportDeclaration, ImportExpression};
+#[cfg(not(test))]
+use oxc_ast::ast::{ExportAllDeclaration, ExportNamedDeclaration};
+#[cfg(not(test))]
+use oxc_ast_visit::Visit;
+#[cfg(not(test))]
+use oxc_parser::Parser;
+#[cfg(not(test))]
+use oxc_span::SourceType;
+#[cfg(not(test))]
+use parcel_sourcemap::{OriginalLocation, SourceMap};
+#[cfg(not(test))]
 use pyo3::{
     basic::CompareOp,
     exceptions::{PyRuntimeError, PyValueError},
@@ -35,6 +61,128 @@ use rolldown_dev::{BundlerConfig, DevEngine, DevOptions, RebuildStrategy};
 #[cfg(not(test))]
 use std::{borrow::Cow, sync::Arc};
 
+#[cfg(not(test))]
+use crate::runtime::run_test_module;
+
+// Reference-counted, cheaply-clonable interned string. Equal values share one
+// allocation through a process-wide dedup table, so the path/import/specifier
+// strings that recur across hundreds of pages cost a single allocation each and
+// only a refcount bump per clone.
+#[derive(Clone)]
+struct Istr(std::sync::Arc<str>);
+
+// The dedup table holds only `Weak` references, bucketed by content hash, so the
+// table never keeps an interned string alive once the last `Istr` pointing at it
+// drops. A dead weak entry (and the allocation behind it) is pruned only when
+// its bucket is next touched by `Istr::new`; buckets that are never touched
+// again, and the hash keys of emptied buckets, stay in the table for the life
+// of the process.
+static INTERN_TABLE: std::sync::LazyLock<
+    std::sync::Mutex<HashMap<u64, Vec<std::sync::Weak<str>>>>,
+> = std::sync::LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+fn intern_hash(value: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    std::hash::Hash::hash(value, &mut hasher);
+    std::hash::Hasher::finish(&hasher)
+}
+
+impl Istr {
+    fn new(value: &str) -> Self {
+        let mut table = INTERN_TABLE.lock().expect("intern table poisoned");
+        let bucket = table.entry(intern_hash(value)).or_default();
+
+        // Drop entries whose last `Istr` has gone away, and reuse a live match
+        // in the same pass.
+        let mut existing = None;
+        bucket.retain(|weak| match weak.upgrade() {
+            Some(shared) => {
+                if existing.is_none() && &*shared == value {
+                    existing = Some(shared);
+                }
+                true
+            }
+            None => false,
+        });
+        if let Some(shared) = existing {
+            return Self(shared);
+        }
+
+        let shared: std::sync::Arc<str> = std::sync::Arc::from(value);
+        bucket.push(std::sync::Arc::downgrade(&shared));
+        Self(shared)
+    }
+}
+
+impl std::ops::Deref for Istr {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::borrow::Borrow<str> for Istr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::hash::Hash for Istr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (*self.0).hash(state);
+    }
+}
+
+impl PartialEq for Istr {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Istr {}
+
+impl PartialEq<str> for Istr {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Istr {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Istr {
+    fn eq(&self, other: &String) -> bool {
+        &*self.0 == other.as_str()
+    }
+}
+
+impl From<&str> for Istr {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for Istr {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl fmt::Debug for Istr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Istr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct PageSpec {
     path: PathBuf,
@@ -58,12 +206,12 @@ pub(crate) struct Page {
 #[derive(Debug, Clone)]
 struct NormalizedPage {
     absolute_path: PathBuf,
-    import: String,
+    import: Istr,
     app: bool,
     ssr: bool,
-    client_name: String,
+    client_name: Istr,
     client_css_path: PathBuf,
-    server_name: Option<String>,
+    server_name: Option<Istr>,
 }
 
 #[derive(Debug, Clone)]
@@ -72,8 +220,86 @@ enum BundleError {
     Runtime(String),
 }
 
+// Tagged build-event protocol streamed to an optional Python callback as each
+// entry is bundled, modelled on Deno's test-runner messages so a caller can
+// render progress and report per-entry failures instead of only seeing a final
+// success or a single aborting error.
+#[cfg(not(test))]
+#[derive(Debug, Clone, deno_core::serde::Serialize)]
+#[serde(
+    tag = "kind",
+    content = "data",
+    rename_all = "snake_case",
+    crate = "deno_core::serde"
+)]
+enum BuildEvent {
+    Plan {
+        total_entries: usize,
+        has_server: bool,
+    },
+    EntryStart {
+        name: String,
+        kind: EntryKind,
+    },
+    EntryDone {
+        name: String,
+        duration_ms: u128,
+        bytes: u64,
+        css_bytes: u64,
+    },
+    EntryFailed {
+        name: String,
+        message: String,
+    },
+    Complete {
+        duration_ms: u128,
+        failed: usize,
+    },
+}
+
+#[cfg(not(test))]
+#[derive(Debug, Clone, Copy, deno_core::serde::Serialize)]
+#[serde(rename_all = "snake_case", crate = "deno_core::serde")]
+enum EntryKind {
+    Client,
+    Server,
+}
+
+// Tagged test-runner events, modelled on Deno's test reporter: a plan up front,
+// a `Wait` as each test file starts, and a `Result` per case the file reports
+// (or a single file-level `Result` when it fails to bundle or run). They are
+// produced over an mpsc channel and forwarded to an optional Python callback.
+#[cfg(not(test))]
+#[derive(Debug, Clone, deno_core::serde::Serialize)]
+#[serde(
+    tag = "kind",
+    content = "data",
+    rename_all = "snake_case",
+    crate = "deno_core::serde"
+)]
+enum TestEvent {
+    Plan { pending: usize, filtered: usize },
+    Wait { name: String },
+    Result { name: String, duration_ms: u128, outcome: TestOutcome },
+}
+
+#[cfg(not(test))]
+#[derive(Debug, Clone, deno_core::serde::Serialize)]
+#[serde(
+    tag = "status",
+    content = "message",
+    rename_all = "snake_case",
+    crate = "deno_core::serde"
+)]
+enum TestOutcome {
+    Ok,
+    Ignored,
+    Failed(String),
+}
+
 const APP_ENTRYPOINT_QUERY: &str = "?gdansk-app-entry";
 const SERVER_ENTRYPOINT_QUERY: &str = "?gdansk-server-entry";
+const TEST_ENTRYPOINT_QUERY: &str = "?gdansk-test-entry";
 const GDANSK_RUNTIME_SPECIFIER: &str = "gdansk:runtime";
 #[cfg(not(test))]
 const GDANSK_CSS_STUB_PREFIX: &str = "gdansk:css-stub:";
@@ -214,50 +440,6 @@ impl fmt::Display for BundleError {
     }
 }
 
-fn extract_quoted_string(input: &str) -> Option<&str> {
-    let first = input.chars().next()?;
-    if first != '"' && first != '\'' {
-        return None;
-    }
-
-    let remainder = &input[first.len_utf8()..];
-    let end = remainder.find(first)?;
-    Some(&remainder[..end])
-}
-
-fn parse_static_js_import_specifier(line: &str) -> Option<&str> {
-    let trimmed = line.trim_start();
-    let remainder = trimmed.strip_prefix("import")?.trim_start();
-    if remainder.is_empty() || remainder.starts_with('(') {
-        return None;
-    }
-
-    if let Some((_, tail)) = remainder.rsplit_once(" from ") {
-        return extract_quoted_string(tail.trim_start());
-    }
-
-    extract_quoted_string(remainder)
-}
-
-fn collect_direct_css_imports(source: &str) -> Vec<String> {
-    source
-        .lines()
-        .filter_map(parse_static_js_import_specifier)
-        .filter(|specifier| specifier.ends_with(".css"))
-        .map(ToOwned::to_owned)
-        .collect()
-}
-
-#[cfg(not(test))]
-fn parse_css_import_specifier(line: &str) -> Option<&str> {
-    let trimmed = line.trim();
-    let remainder = trimmed.strip_prefix("@import")?.trim_start();
-    if remainder.starts_with("url(") {
-        return None;
-    }
-    extract_quoted_string(remainder)
-}
-
 #[cfg(not(test))]
 fn canonicalize_existing_file(path: &Path, label: &str) -> Result<PathBuf, BundleError> {
     if !path.exists() {
@@ -359,12 +541,35 @@ fn resolve_css_import_path(
     canonicalize_existing_file(&style_path, "css import")
 }
 
+// The inlined CSS text together with the provenance of each emitted line, so a
+// source map can point back at the originating file and line even after the
+// printer has collapsed everything into a single stylesheet.
+#[cfg(not(test))]
+#[derive(Default)]
+struct CssBundle {
+    css: String,
+    line_origins: Vec<(PathBuf, u32)>,
+    // Every file read while inlining, including pure-`@import` barrels that emit
+    // no rules of their own, so the dependency graph still invalidates them.
+    reads: Vec<PathBuf>,
+}
+
+#[cfg(not(test))]
+impl CssBundle {
+    fn push_line(&mut self, text: &str, source: &Path, original_line: u32) {
+        self.css.push_str(text);
+        self.css.push('\n');
+        self.line_origins.push((source.to_path_buf(), original_line));
+    }
+}
+
 #[cfg(not(test))]
 fn bundle_css_file(
     file_path: &Path,
     cwd: &Path,
     stack: &mut Vec<PathBuf>,
-) -> Result<String, BundleError> {
+    out: &mut CssBundle,
+) -> Result<(), BundleError> {
     if stack.iter().any(|candidate| candidate == file_path) {
         return Err(BundleError::runtime(format!(
             "detected cyclic css import: {}",
@@ -373,6 +578,7 @@ fn bundle_css_file(
     }
 
     stack.push(file_path.to_path_buf());
+    out.reads.push(file_path.to_path_buf());
 
     let source = fs::read_to_string(file_path).map_err(|err| {
         BundleError::runtime(format!(
@@ -381,119 +587,767 @@ fn bundle_css_file(
         ))
     })?;
     let importer_dir = file_path.parent().unwrap_or(cwd);
-    let mut bundled = String::new();
-
-    for line in source.lines() {
-        if let Some(specifier) = parse_css_import_specifier(line) {
-            let resolved = resolve_css_import_path(specifier, importer_dir, cwd)?;
-            let imported_css = bundle_css_file(&resolved, cwd, stack)?;
-            bundled.push_str(&imported_css);
-            if !imported_css.ends_with('\n') {
-                bundled.push('\n');
+
+    // Parse the stylesheet and walk its rules rather than scanning lines, so an
+    // `@import` whose specifier or media condition is split across lines is read
+    // from the AST instead of being corrupted by a naive text match. Each
+    // `@import` target is resolved and its rules spliced in place; the file's
+    // own rules are re-serialized and appended.
+    let parser_options = ParserOptions {
+        filename: file_path.to_string_lossy().into_owned(),
+        ..Default::default()
+    };
+    let mut stylesheet = StyleSheet::parse(&source, parser_options).map_err(|err| {
+        BundleError::runtime(format!("failed to parse css file {}: {err}", file_path.display()))
+    })?;
+
+    let mut emitted_line = 0u32;
+    for rule in std::mem::take(&mut stylesheet.rules.0) {
+        match rule {
+            CssRule::Import(import) => {
+                let resolved = resolve_css_import_path(&import.url, importer_dir, cwd)?;
+                if import.media.media_queries.is_empty() {
+                    bundle_css_file(&resolved, cwd, stack, out)?;
+                } else {
+                    // Preserve the media-query condition by wrapping the inlined
+                    // rules; the printer collapses this back into the enclosing
+                    // stylesheet during minification.
+                    let media = import
+                        .media
+                        .to_css_string(PrinterOptions::default())
+                        .map_err(|err| {
+                            BundleError::runtime(format!(
+                                "failed to print css media condition in {}: {err}",
+                                file_path.display()
+                            ))
+                        })?;
+                    out.push_line(&format!("@media {media} {{"), file_path, emitted_line);
+                    bundle_css_file(&resolved, cwd, stack, out)?;
+                    out.push_line("}", file_path, emitted_line);
+                }
+            }
+            other => {
+                let text = other.to_css_string(PrinterOptions::default()).map_err(|err| {
+                    BundleError::runtime(format!(
+                        "failed to print css rule in {}: {err}",
+                        file_path.display()
+                    ))
+                })?;
+                for line in text.lines() {
+                    out.push_line(line, file_path, emitted_line);
+                    emitted_line += 1;
+                }
             }
-            continue;
         }
-
-        bundled.push_str(line);
-        bundled.push('\n');
     }
 
     let _ = stack.pop();
-    Ok(bundled)
+    Ok(())
 }
 
+// A `.module.css` id suffix opts a stylesheet into CSS Modules scoping.
+#[cfg(not(test))]
+const CSS_MODULE_SUFFIX: &str = ".module.css";
+
+// Resolved CSS-module file -> JSON object mapping original class/id names to
+// their scoped names, consumed by `GdanskCssStubPlugin` to back the default
+// export of `import styles from "./x.module.css"`.
 #[cfg(not(test))]
-fn maybe_minify_css(css: String, minify: bool) -> String {
-    if !minify {
-        return css;
+type CssModuleExports = Arc<HashMap<PathBuf, String>>;
+
+// Resolved CSS file -> its compiled stylesheet text, used by `compile` mode so
+// `GdanskCssStubPlugin` can inject each page's styles as a runtime `<style>`
+// instead of emitting a sibling `.css` asset.
+#[cfg(not(test))]
+type CssInlineStyles = Arc<HashMap<PathBuf, String>>;
+
+// Parse a `.module.css` file with CSS Modules enabled, rewriting each local
+// class/id selector to a collision-free `<name>_<local>__<hash>` name while
+// leaving `:global(...)` selectors untouched. Returns the scoped CSS together
+// with a JSON map of original -> scoped names for the importing JS.
+#[cfg(not(test))]
+fn scope_css_module(file_path: &Path, targets: Targets) -> Result<(String, String), BundleError> {
+    let source = fs::read_to_string(file_path).map_err(|err| {
+        BundleError::runtime(format!(
+            "failed to read css module {}: {err}",
+            file_path.display()
+        ))
+    })?;
+
+    let pattern = Pattern::parse("[name]_[local]__[hash]")
+        .map_err(|err| BundleError::runtime(format!("invalid css module pattern: {err}")))?;
+    let filename = file_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("styles")
+        .to_string();
+    let parser_options = ParserOptions {
+        filename,
+        css_modules: Some(CssModulesConfig {
+            pattern,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let stylesheet = StyleSheet::parse(&source, parser_options)
+        .map_err(|err| BundleError::runtime(format!("failed to parse css module: {err}")))?;
+    let printed = stylesheet
+        .to_css(PrinterOptions {
+            targets,
+            ..Default::default()
+        })
+        .map_err(|err| BundleError::runtime(format!("failed to print css module: {err}")))?;
+
+    let mut names = deno_core::serde_json::Map::new();
+    for (original, export) in printed.exports.unwrap_or_default() {
+        names.insert(original, Value::String(export.name));
     }
+    let json = deno_core::serde_json::to_string(&Value::Object(names))
+        .map_err(|err| BundleError::runtime(format!("failed to serialize css module map: {err}")))?;
 
-    let mut compact = String::new();
-    for line in css.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
+    Ok((printed.code, json))
+}
+
+// Map every emitted line of the inlined bundle back to its originating file and
+// line. Passed to the printer via `extends` so the mappings survive
+// minification instead of pointing at the throwaway concatenated text.
+#[cfg(not(test))]
+fn input_source_map(bundle: &CssBundle) -> SourceMap {
+    let mut map = SourceMap::new("/");
+    let mut source_indices: HashMap<PathBuf, u32> = HashMap::new();
+    for (line, (path, original_line)) in bundle.line_origins.iter().enumerate() {
+        let source_index = *source_indices.entry(path.clone()).or_insert_with(|| {
+            map.add_source(&path.to_string_lossy())
+        });
+        map.add_mapping(
+            line as u32,
+            0,
+            Some(OriginalLocation {
+                original_line: *original_line,
+                original_column: 0,
+                source: source_index,
+                name: None,
+            }),
+        );
+    }
+    map
+}
+
+// Parse the fully-inlined CSS into a stylesheet AST and serialize it once
+// through the printer, so minification collapses whitespace, merges/dedupes
+// declarations, and drops comments without the corruption a line-based pass
+// caused. `targets` downlevels modern syntax and emits vendor prefixes for the
+// configured browsers. When `sourcemaps` is set the second element carries the
+// serialized source map and the returned CSS ends in a `sourceMappingURL`
+// comment pointing at the sibling `.map` file.
+#[cfg(not(test))]
+fn render_css(
+    bundle: &CssBundle,
+    minify: bool,
+    targets: Targets,
+    sourcemaps: bool,
+    map_file_name: &str,
+) -> Result<(String, Option<String>), BundleError> {
+    let mut stylesheet = StyleSheet::parse(&bundle.css, ParserOptions::default())
+        .map_err(|err| BundleError::runtime(format!("failed to parse bundled css: {err}")))?;
+
+    if minify {
+        stylesheet
+            .minify(MinifyOptions {
+                targets,
+                ..Default::default()
+            })
+            .map_err(|err| BundleError::runtime(format!("failed to minify css: {err}")))?;
+    }
+
+    if !sourcemaps {
+        let printed = stylesheet
+            .to_css(PrinterOptions {
+                minify,
+                targets,
+                ..Default::default()
+            })
+            .map_err(|err| BundleError::runtime(format!("failed to print css: {err}")))?;
+        return Ok((printed.code, None));
+    }
+
+    let mut printer_map = SourceMap::new("/");
+    let printed = stylesheet
+        .to_css(PrinterOptions {
+            minify,
+            targets,
+            source_map: Some(&mut printer_map),
+            ..Default::default()
+        })
+        .map_err(|err| BundleError::runtime(format!("failed to print css: {err}")))?;
+
+    // Compose printed -> inlined-bundle (printer_map) with inlined-bundle ->
+    // original files (input map) to get printed -> original files.
+    printer_map
+        .extends(&input_source_map(bundle))
+        .map_err(|err| BundleError::runtime(format!("failed to compose css source map: {err}")))?;
+    let json = printer_map
+        .to_json(None)
+        .map_err(|err| BundleError::runtime(format!("failed to serialize css source map: {err}")))?;
+
+    let mut code = printed.code;
+    if !code.ends_with('\n') {
+        code.push('\n');
+    }
+    code.push_str(&format!("/*# sourceMappingURL={map_file_name} */\n"));
+    Ok((code, Some(json)))
+}
+
+// Extensions tried, in priority order, when resolving an extensionless JS/TS
+// module specifier to a file on disk.
+#[cfg(not(test))]
+const JS_MODULE_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mjs", "cjs"];
+
+// Collects every import/re-export/`import()` specifier in a module, including
+// those nested inside expressions, so the module-graph walk sees the same edges
+// the JS bundle does.
+#[cfg(not(test))]
+#[derive(Default)]
+struct SpecifierCollector {
+    specifiers: Vec<String>,
+}
+
+#[cfg(not(test))]
+impl<'a> Visit<'a> for SpecifierCollector {
+    fn visit_import_declaration(&mut self, decl: &ImportDeclaration<'a>) {
+        self.specifiers.push(decl.source.value.to_string());
+    }
+
+    fn visit_export_named_declaration(&mut self, decl: &ExportNamedDeclaration<'a>) {
+        if let Some(source) = &decl.source {
+            self.specifiers.push(source.value.to_string());
         }
-        compact.push_str(trimmed);
     }
-    if !compact.is_empty() {
-        compact.push('\n');
+
+    fn visit_export_all_declaration(&mut self, decl: &ExportAllDeclaration<'a>) {
+        self.specifiers.push(decl.source.value.to_string());
+    }
+
+    fn visit_import_expression(&mut self, expr: &ImportExpression<'a>) {
+        if let Expression::StringLiteral(literal) = &expr.source {
+            self.specifiers.push(literal.value.to_string());
+        }
+        oxc_ast_visit::walk::walk_import_expression(self, expr);
     }
-    compact
 }
 
 #[cfg(not(test))]
-fn build_css_outputs(
-    normalized: &[NormalizedPage],
+fn parse_module_specifiers(source: &str, path: &Path) -> Vec<String> {
+    let allocator = Allocator::default();
+    let source_type = SourceType::from_path(path).unwrap_or_default();
+    let parsed = Parser::new(&allocator, source, source_type).parse();
+    let mut collector = SpecifierCollector::default();
+    collector.visit_program(&parsed.program);
+    collector.specifiers
+}
+
+#[cfg(not(test))]
+fn resolve_module_file(candidate: &Path) -> Option<PathBuf> {
+    let canonical = |path: &Path| {
+        path.canonicalize()
+            .ok()
+            .map(|resolved| dunce::simplified(&resolved).to_path_buf())
+    };
+
+    if candidate.is_file() {
+        return canonical(candidate);
+    }
+    for extension in JS_MODULE_EXTENSIONS {
+        let with_extension = candidate.with_extension(extension);
+        if with_extension.is_file() {
+            return canonical(&with_extension);
+        }
+    }
+    for extension in JS_MODULE_EXTENSIONS {
+        let index = candidate.join(format!("index.{extension}"));
+        if index.is_file() {
+            return canonical(&index);
+        }
+    }
+    None
+}
+
+#[cfg(not(test))]
+fn resolve_module_specifier(specifier: &str, importer_dir: &Path, cwd: &Path) -> Option<PathBuf> {
+    if specifier.starts_with("./") || specifier.starts_with("../") {
+        return resolve_module_file(&importer_dir.join(specifier));
+    }
+    let package_path = resolve_node_modules_path(specifier, importer_dir, cwd)?;
+    resolve_module_file(&package_path)
+}
+
+// Walks the JS/TS module graph rooted at `entry`, recording every transitively
+// imported `.css` file in post-order (dependencies before dependents) with
+// duplicates removed. `visited` both dedupes modules and breaks import cycles,
+// mirroring the `stack` guard in `bundle_css_file`.
+#[cfg(not(test))]
+fn collect_module_css_imports(
+    entry: &Path,
     cwd: &Path,
-    output_dir: &Path,
-    minify: bool,
+    visited: &mut HashSet<PathBuf>,
+    css: &mut Vec<PathBuf>,
 ) -> Result<(), BundleError> {
-    let output_root = if output_dir.is_absolute() {
-        output_dir.to_path_buf()
-    } else {
-        cwd.join(output_dir)
-    };
+    if !visited.insert(entry.to_path_buf()) {
+        return Ok(());
+    }
 
-    for page in normalized {
-        let entry_source = fs::read_to_string(&page.absolute_path).map_err(|err| {
-            BundleError::runtime(format!(
-                "failed to read entry source {}: {err}",
-                page.absolute_path.display()
-            ))
-        })?;
-        let css_imports = collect_direct_css_imports(&entry_source);
-        let output_path = output_root.join(&page.client_css_path);
+    let source = fs::read_to_string(entry).map_err(|err| {
+        BundleError::runtime(format!("failed to read module {}: {err}", entry.display()))
+    })?;
+    let importer_dir = entry.parent().unwrap_or(cwd);
+
+    let mut direct_css = Vec::new();
+    let mut child_modules = Vec::new();
+    for specifier in parse_module_specifiers(&source, entry) {
+        if specifier.ends_with(".css") {
+            direct_css.push(specifier);
+        } else if let Some(module_path) = resolve_module_specifier(&specifier, importer_dir, cwd) {
+            child_modules.push(module_path);
+        }
+    }
 
-        if css_imports.is_empty() {
-            if output_path.exists() {
-                fs::remove_file(&output_path).map_err(|err| {
-                    BundleError::runtime(format!(
-                        "failed to remove stale css output {}: {err}",
-                        output_path.display()
-                    ))
-                })?;
-            }
+    for module_path in child_modules {
+        collect_module_css_imports(&module_path, cwd, visited, css)?;
+    }
+    for specifier in direct_css {
+        let resolved = resolve_css_import_path(&specifier, importer_dir, cwd)?;
+        if !css.contains(&resolved) {
+            css.push(resolved);
+        }
+    }
+
+    Ok(())
+}
+
+// A dependency file slated for vendoring, plus the layout-stable path it will
+// occupy under `vendor/`. `vendor_relative` mirrors the source's location
+// relative to its owning `node_modules` directory so a package keeps its
+// internal structure and its relative imports keep resolving after the copy.
+#[cfg(not(test))]
+#[derive(Debug, Default)]
+struct VendorPlan {
+    // Canonical source file -> its path relative to `vendor/`.
+    files: HashMap<PathBuf, String>,
+    // Bare specifier -> vendored path of the file it resolves to.
+    imports: Vec<(String, String)>,
+    // Guards against two different source files claiming the same vendored path.
+    claimed: HashMap<String, PathBuf>,
+}
+
+// Walks the JS/TS module graph rooted at `entry`, recording every file that
+// lives under a `node_modules` directory so it can be copied into `vendor/`,
+// and every bare specifier so it can be pinned in the emitted import map.
+// Relative imports are followed but not themselves vendored unless they sit
+// inside a vendored package. `visited` dedupes modules and breaks cycles,
+// mirroring [`collect_module_css_imports`].
+#[cfg(not(test))]
+fn collect_vendor_plan(
+    entry: &Path,
+    cwd: &Path,
+    visited: &mut HashSet<PathBuf>,
+    plan: &mut VendorPlan,
+) -> Result<(), BundleError> {
+    if !visited.insert(entry.to_path_buf()) {
+        return Ok(());
+    }
+
+    let source = fs::read_to_string(entry).map_err(|err| {
+        BundleError::runtime(format!("failed to read module {}: {err}", entry.display()))
+    })?;
+    let importer_dir = entry.parent().unwrap_or(cwd);
+
+    for specifier in parse_module_specifiers(&source, entry) {
+        if specifier.ends_with(".css") {
             continue;
         }
+        if is_remote_specifier(&specifier) {
+            return Err(BundleError::validation(format!(
+                "cannot vendor remote dependency {specifier:?}: pre-fetch it into node_modules first"
+            )));
+        }
 
-        let entry_dir = page.absolute_path.parent().ok_or_else(|| {
-            BundleError::runtime(format!(
-                "entry source does not have a parent directory: {}",
-                page.absolute_path.display()
-            ))
-        })?;
-        let mut bundled = String::new();
+        let is_relative = specifier.starts_with("./") || specifier.starts_with("../");
+        let Some(resolved) = resolve_module_specifier(&specifier, importer_dir, cwd) else {
+            continue;
+        };
 
-        for specifier in css_imports {
-            let css_path = resolve_css_import_path(&specifier, entry_dir, cwd)?;
-            let mut stack = Vec::new();
-            let css = bundle_css_file(&css_path, cwd, &mut stack)?;
-            bundled.push_str(&css);
-            if !css.ends_with('\n') {
-                bundled.push('\n');
+        if let Some(vendor_relative) = vendored_relative(&resolved) {
+            plan.claim(&vendor_relative, &resolved)?;
+            plan.files.insert(resolved.clone(), vendor_relative.clone());
+            if !is_relative && !plan.imports.iter().any(|(name, _)| name == &specifier) {
+                plan.imports.push((specifier.clone(), vendor_relative));
             }
         }
 
-        if let Some(parent) = output_path.parent() {
-            fs::create_dir_all(parent).map_err(|err| {
+        collect_vendor_plan(&resolved, cwd, visited, plan)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(test))]
+impl VendorPlan {
+    // Reserve a vendored path for a source file, rejecting the case where two
+    // distinct files would be copied to the same name (the vendoring analogue of
+    // the `output_collisions` guard in `normalize_pages`).
+    fn claim(&mut self, vendor_relative: &str, source: &Path) -> Result<(), BundleError> {
+        match self.claimed.get(vendor_relative) {
+            Some(existing) if existing != source => Err(BundleError::validation(format!(
+                "two dependencies vendor to the same path {vendor_relative}: {} and {}",
+                existing.display(),
+                source.display()
+            ))),
+            Some(_) => Ok(()),
+            None => {
+                self.claimed
+                    .insert(vendor_relative.to_string(), source.to_path_buf());
+                Ok(())
+            }
+        }
+    }
+}
+
+// `true` for URL-style specifiers the offline vendor pass cannot fetch.
+fn is_remote_specifier(specifier: &str) -> bool {
+    specifier.starts_with("http://")
+        || specifier.starts_with("https://")
+        || specifier.starts_with("npm:")
+        || specifier.starts_with("jsr:")
+}
+
+// Mirror a resolved dependency's location relative to the `node_modules`
+// directory that owns it (e.g. `.../node_modules/@ui/button/index.js` ->
+// `@ui/button/index.js`), giving each vendored file a stable, collision-aware
+// path. Returns `None` for files that do not live under any `node_modules`.
+fn vendored_relative(resolved: &Path) -> Option<String> {
+    let mut components = Vec::new();
+    let mut after_node_modules = false;
+    for component in resolved.components() {
+        let part = component.as_os_str().to_str()?;
+        if part == "node_modules" {
+            // A nested `node_modules` owns everything below it.
+            after_node_modules = true;
+            components.clear();
+        } else if after_node_modules {
+            components.push(part);
+        }
+    }
+    if components.is_empty() {
+        return None;
+    }
+    Some(components.join("/"))
+}
+
+// Records, per emitted stylesheet, every file that was read to produce it (the
+// page's JS modules plus each inlined `@import` target and package style file)
+// so a dev rebuild can touch only the outputs a changed file actually feeds.
+#[cfg(not(test))]
+#[derive(Debug, Default, Clone)]
+struct CssDependencyGraph {
+    deps: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+#[cfg(not(test))]
+impl CssDependencyGraph {
+    // Drop outputs whose imports have emptied out (mirroring the single-build
+    // stale-removal), otherwise replace the recorded dependency set.
+    fn record(&mut self, output: PathBuf, deps: HashSet<PathBuf>) {
+        if deps.is_empty() {
+            self.deps.remove(&output);
+        } else {
+            self.deps.insert(output, deps);
+        }
+    }
+
+    // Outputs whose dependency set contains at least one changed file.
+    fn affected(&self, changed: &HashSet<PathBuf>) -> HashSet<PathBuf> {
+        self.deps
+            .iter()
+            .filter(|(_, deps)| !deps.is_disjoint(changed))
+            .map(|(output, _)| output.clone())
+            .collect()
+    }
+}
+
+// Compile a single CSS file to a standalone stylesheet string (no source map),
+// scoping it first when it is a `.module.css`, and return it together with every
+// file read to produce it. Used by `compile` mode to inline each direct CSS
+// import as a `<style>` rather than emit a sibling asset.
+#[cfg(not(test))]
+fn compile_inline_css(
+    css_path: &Path,
+    cwd: &Path,
+    minify: bool,
+    targets: Targets,
+    module_exports: &mut HashMap<PathBuf, String>,
+) -> Result<(String, Vec<PathBuf>), BundleError> {
+    let mut bundle = CssBundle::default();
+    if css_path.to_string_lossy().ends_with(CSS_MODULE_SUFFIX) {
+        let (scoped, json) = scope_css_module(css_path, targets)?;
+        for (index, line) in scoped.lines().enumerate() {
+            bundle.push_line(line, css_path, index as u32);
+        }
+        bundle.reads.push(css_path.to_path_buf());
+        module_exports.insert(css_path.to_path_buf(), json);
+    } else {
+        let mut stack = Vec::new();
+        bundle_css_file(css_path, cwd, &mut stack, &mut bundle)?;
+    }
+    let (printed, _) = render_css(&bundle, minify, targets, false, "")?;
+    Ok((printed, bundle.reads))
+}
+
+// Bundle a single page's stylesheet, writing the `.css` (and optional `.map`)
+// or removing a now-empty output, and return the absolute paths of every file
+// read so the caller can track them in the dependency graph.
+#[cfg(not(test))]
+fn build_page_css(
+    page: &NormalizedPage,
+    output_root: &Path,
+    cwd: &Path,
+    minify: bool,
+    sourcemaps: bool,
+    targets: Targets,
+    module_exports: &mut HashMap<PathBuf, String>,
+    inline_styles: Option<&mut HashMap<PathBuf, String>>,
+) -> Result<HashSet<PathBuf>, BundleError> {
+    let output_path = output_root.join(&page.client_css_path);
+
+    let mut dependencies = HashSet::new();
+    let mut css_files = Vec::new();
+    collect_module_css_imports(&page.absolute_path, cwd, &mut dependencies, &mut css_files)?;
+
+    // Compile mode: inline each CSS file for `<style>` injection and emit no
+    // sibling `.css` asset.
+    if let Some(inline_styles) = inline_styles {
+        for css_path in css_files {
+            let (compiled, reads) =
+                compile_inline_css(&css_path, cwd, minify, targets, module_exports)?;
+            dependencies.extend(reads);
+            inline_styles.insert(css_path, compiled);
+        }
+        return Ok(dependencies);
+    }
+
+    if css_files.is_empty() {
+        if output_path.exists() {
+            fs::remove_file(&output_path).map_err(|err| {
                 BundleError::runtime(format!(
-                    "failed to create css output directory {}: {err}",
-                    parent.display()
+                    "failed to remove stale css output {}: {err}",
+                    output_path.display()
                 ))
             })?;
         }
-        fs::write(&output_path, maybe_minify_css(bundled, minify)).map_err(|err| {
+        // Keep watching the page's JS modules so re-adding a `.css` import rebuilds.
+        return Ok(dependencies);
+    }
+
+    let mut bundle = CssBundle::default();
+
+    for css_path in css_files {
+        if css_path.to_string_lossy().ends_with(CSS_MODULE_SUFFIX) {
+            let (scoped, json) = scope_css_module(&css_path, targets)?;
+            for (index, line) in scoped.lines().enumerate() {
+                bundle.push_line(line, &css_path, index as u32);
+            }
+            bundle.reads.push(css_path.clone());
+            module_exports.insert(css_path, json);
+        } else {
+            let mut stack = Vec::new();
+            bundle_css_file(&css_path, cwd, &mut stack, &mut bundle)?;
+        }
+    }
+
+    // Every inlined `@import` target (including nested ones and pure-import
+    // barrels) is recorded in `reads`, so the full read set is the union of JS
+    // modules and these.
+    dependencies.extend(bundle.reads.iter().cloned());
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            BundleError::runtime(format!(
+                "failed to create css output directory {}: {err}",
+                parent.display()
+            ))
+        })?;
+    }
+
+    let map_path = output_path.with_extension("css.map");
+    let map_file_name = map_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_owned();
+    let (printed, source_map) = render_css(&bundle, minify, targets, sourcemaps, &map_file_name)?;
+    fs::write(&output_path, printed).map_err(|err| {
+        BundleError::runtime(format!(
+            "failed to write css output {}: {err}",
+            output_path.display()
+        ))
+    })?;
+    if let Some(source_map) = source_map {
+        fs::write(&map_path, source_map).map_err(|err| {
             BundleError::runtime(format!(
-                "failed to write css output {}: {err}",
-                output_path.display()
+                "failed to write css source map {}: {err}",
+                map_path.display()
             ))
         })?;
     }
 
+    Ok(dependencies)
+}
+
+#[cfg(not(test))]
+fn css_output_root(cwd: &Path, output_dir: &Path) -> PathBuf {
+    if output_dir.is_absolute() {
+        output_dir.to_path_buf()
+    } else {
+        cwd.join(output_dir)
+    }
+}
+
+#[cfg(not(test))]
+fn build_css_outputs(
+    normalized: &[NormalizedPage],
+    cwd: &Path,
+    output_dir: &Path,
+    minify: bool,
+    sourcemaps: bool,
+    targets: Targets,
+    single_file: bool,
+) -> Result<(HashMap<PathBuf, String>, CssDependencyGraph, HashMap<PathBuf, String>), BundleError> {
+    let output_root = css_output_root(cwd, output_dir);
+    let mut module_exports: HashMap<PathBuf, String> = HashMap::new();
+    let mut inline_styles: HashMap<PathBuf, String> = HashMap::new();
+    let mut graph = CssDependencyGraph::default();
+
+    for page in normalized {
+        let output_path = output_root.join(&page.client_css_path);
+        let dependencies = build_page_css(
+            page,
+            &output_root,
+            cwd,
+            minify,
+            sourcemaps,
+            targets,
+            &mut module_exports,
+            single_file.then_some(&mut inline_styles),
+        )?;
+        graph.record(output_path, dependencies);
+    }
+
+    Ok((module_exports, graph, inline_styles))
+}
+
+// Re-bundle only the page outputs whose recorded dependency set intersects the
+// changed files, patching the graph in place. Driven by the dev-mode CSS
+// watcher so edits to a shared stylesheet rebuild exactly the pages that use it.
+#[cfg(not(test))]
+fn rebuild_css_for_changes(
+    normalized: &[NormalizedPage],
+    cwd: &Path,
+    output_dir: &Path,
+    minify: bool,
+    sourcemaps: bool,
+    targets: Targets,
+    graph: &mut CssDependencyGraph,
+    module_exports: &mut HashMap<PathBuf, String>,
+    changed: &HashSet<PathBuf>,
+) -> Result<(), BundleError> {
+    let affected = graph.affected(changed);
+    if affected.is_empty() {
+        return Ok(());
+    }
+
+    let output_root = css_output_root(cwd, output_dir);
+    for page in normalized {
+        let output_path = output_root.join(&page.client_css_path);
+        if affected.contains(&output_path) {
+            let dependencies = build_page_css(
+                page,
+                &output_root,
+                cwd,
+                minify,
+                sourcemaps,
+                targets,
+                module_exports,
+                None,
+            )?;
+            graph.record(output_path, dependencies);
+        }
+    }
+
     Ok(())
 }
 
+// Watch the project tree in dev mode and incrementally re-bundle CSS through the
+// dependency graph, so edits to a page entry or any transitively imported
+// stylesheet rebuild only the affected page outputs. Runs on its own thread for
+// the lifetime of the dev session.
+#[cfg(not(test))]
+fn spawn_css_dev_watcher(
+    normalized: Vec<NormalizedPage>,
+    cwd: PathBuf,
+    output_dir: PathBuf,
+    minify: bool,
+    sourcemaps: bool,
+    mut graph: CssDependencyGraph,
+    mut module_exports: HashMap<PathBuf, String>,
+) {
+    std::thread::spawn(move || {
+        let targets = Targets::default();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let Ok(mut watcher) = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        }) else {
+            return;
+        };
+        if watcher.watch(&cwd, RecursiveMode::Recursive).is_err() {
+            return;
+        }
+
+        for event in rx {
+            let Ok(event) = event else { continue };
+            let changed: HashSet<PathBuf> = event
+                .paths
+                .into_iter()
+                .map(|path| {
+                    // A deleted stylesheet (e.g. a shared `@import` target being
+                    // removed) no longer canonicalizes; fall back to the raw
+                    // event path so it still invalidates the pages that import
+                    // it instead of letting the incremental graph go stale.
+                    match path.canonicalize() {
+                        Ok(resolved) => dunce::simplified(&resolved).to_path_buf(),
+                        Err(_) => dunce::simplified(&path).to_path_buf(),
+                    }
+                })
+                .collect();
+            if changed.is_empty() {
+                continue;
+            }
+            let _ = rebuild_css_for_changes(
+                &normalized,
+                &cwd,
+                &output_dir,
+                minify,
+                sourcemaps,
+                targets,
+                &mut graph,
+                &mut module_exports,
+                &changed,
+            );
+        }
+
+        drop(watcher);
+    });
+}
+
 fn entry_import_for_client(import: &str, app: bool) -> String {
     if app {
         format!("{import}{APP_ENTRYPOINT_QUERY}")
@@ -506,19 +1360,19 @@ fn entry_import_for_server(import: &str) -> String {
     format!("{import}{SERVER_ENTRYPOINT_QUERY}")
 }
 
-fn build_client_input_item_fields(normalized: &[NormalizedPage]) -> Vec<(String, String)> {
+fn build_client_input_item_fields(normalized: &[NormalizedPage]) -> Vec<(Istr, Istr)> {
     normalized
         .iter()
         .map(|item| {
             (
                 item.client_name.clone(),
-                entry_import_for_client(&item.import, item.app),
+                Istr::from(entry_import_for_client(&item.import, item.app)),
             )
         })
         .collect()
 }
 
-fn build_server_input_item_fields(normalized: &[NormalizedPage]) -> Vec<(String, String)> {
+fn build_server_input_item_fields(normalized: &[NormalizedPage]) -> Vec<(Istr, Istr)> {
     normalized
         .iter()
         .filter(|item| item.ssr)
@@ -527,7 +1381,7 @@ fn build_server_input_item_fields(normalized: &[NormalizedPage]) -> Vec<(String,
                 item.server_name
                     .clone()
                     .expect("ssr page must have server name"),
-                entry_import_for_server(&item.import),
+                Istr::from(entry_import_for_server(&item.import)),
             )
         })
         .collect()
@@ -547,18 +1401,104 @@ setSsrHtml(renderToString(createElement(App)));
     ))
 }
 
+// Wrap a discovered test module so the registered `test(name, fn)` cases run
+// under the Deno-backed runtime and each outcome is streamed back through the
+// `reportTestResult` runtime op. The test module is loaded with a dynamic import
+// so `globalThis.test` is defined before its body registers any cases.
+#[cfg(not(test))]
+fn test_harness_wrapper_source(source_id: &str) -> Option<String> {
+    let file_name = Path::new(source_id).file_name()?.to_str()?;
+    let import_path = format!("./{file_name}");
+    Some(format!(
+        r#"import {{ reportTestResult }} from "gdansk:runtime";
+
+const cases = [];
+globalThis.test = (name, fn) => {{ cases.push({{ name, fn }}); }};
+
+await import("{import_path}");
+
+for (const {{ name, fn }} of cases) {{
+  const started = Date.now();
+  try {{
+    await fn();
+    reportTestResult(name, "ok", Date.now() - started, null);
+  }} catch (error) {{
+    const message = error && error.stack ? error.stack : String(error);
+    reportTestResult(name, "failed", Date.now() - started, message);
+  }}
+}}
+"#
+    ))
+}
+
 #[cfg(not(test))]
 struct DevEngineCloseGuard {
     engine: Option<Arc<DevEngine>>,
 }
 
+#[cfg(not(test))]
+const GDANSK_CSS_STUB_MODULE_PREFIX: &str = "gdansk:css-stub:module:";
+#[cfg(not(test))]
+const GDANSK_CSS_STUB_INLINE_PREFIX: &str = "gdansk:css-stub:inline:";
+
 #[cfg(not(test))]
 #[derive(Debug, Default)]
-struct GdanskCssStubPlugin;
+struct GdanskCssStubPlugin {
+    module_exports: CssModuleExports,
+    // Present in `compile` mode: the per-file stylesheet text to inline as a
+    // runtime `<style>` instead of emitting a sibling `.css` asset.
+    inline_styles: Option<CssInlineStyles>,
+}
 
 #[cfg(not(test))]
 impl GdanskCssStubPlugin {
-    fn resolve_virtual_id(specifier: &str, importer: Option<&str>) -> String {
+    fn new(module_exports: CssModuleExports) -> Self {
+        Self {
+            module_exports,
+            inline_styles: None,
+        }
+    }
+
+    fn with_inline_styles(module_exports: CssModuleExports, inline_styles: CssInlineStyles) -> Self {
+        Self {
+            module_exports,
+            inline_styles: Some(inline_styles),
+        }
+    }
+
+    // A `.module.css` specifier that resolves to a file with a recorded export
+    // map gets a stub id carrying its absolute path, so `load` can hand back the
+    // scoped-name map. Everything else falls back to an opaque hashed id.
+    fn module_path(&self, specifier: &str, importer: Option<&str>) -> Option<PathBuf> {
+        if !specifier.ends_with(CSS_MODULE_SUFFIX) {
+            return None;
+        }
+        let importer_dir = Path::new(importer?).parent()?;
+        let canonical = importer_dir.join(specifier).canonicalize().ok()?;
+        let canonical = dunce::simplified(&canonical).to_path_buf();
+        self.module_exports
+            .contains_key(&canonical)
+            .then_some(canonical)
+    }
+
+    // In compile mode, the absolute path of a CSS import whose compiled text is
+    // queued for inlining, so `load` can emit its `<style>` injection.
+    fn inline_path(&self, specifier: &str, importer: Option<&str>) -> Option<PathBuf> {
+        let inline_styles = self.inline_styles.as_ref()?;
+        let importer_dir = Path::new(importer?).parent()?;
+        let canonical = importer_dir.join(specifier).canonicalize().ok()?;
+        let canonical = dunce::simplified(&canonical).to_path_buf();
+        inline_styles.contains_key(&canonical).then_some(canonical)
+    }
+
+    fn resolve_virtual_id(&self, specifier: &str, importer: Option<&str>) -> String {
+        if let Some(path) = self.inline_path(specifier, importer) {
+            return format!("{GDANSK_CSS_STUB_INLINE_PREFIX}{}", path.display());
+        }
+        if let Some(path) = self.module_path(specifier, importer) {
+            return format!("{GDANSK_CSS_STUB_MODULE_PREFIX}{}", path.display());
+        }
+
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
         std::hash::Hash::hash(&importer, &mut hasher);
         std::hash::Hash::hash(&specifier, &mut hasher);
@@ -567,6 +1507,29 @@ impl GdanskCssStubPlugin {
             std::hash::Hasher::finish(&hasher)
         )
     }
+
+    // The JS module served for an inlined stylesheet: append a `<style>` element
+    // carrying the compiled CSS, re-exporting the scoped-name map when the file
+    // is also a CSS module.
+    fn inline_style_module(&self, path: &str) -> Option<String> {
+        let inline_styles = self.inline_styles.as_ref()?;
+        let css = inline_styles.get(Path::new(path))?;
+        let literal = deno_core::serde_json::to_string(css).ok()?;
+        let exports = match self.module_exports.get(Path::new(path)) {
+            Some(json) => format!("export default {json};"),
+            None => "export {};".to_string(),
+        };
+        Some(format!(
+            r#"const css = {literal};
+if (typeof document !== "undefined") {{
+  const style = document.createElement("style");
+  style.textContent = css;
+  document.head.appendChild(style);
+}}
+{exports}
+"#
+        ))
+    }
 }
 
 #[cfg(not(test))]
@@ -585,11 +1548,29 @@ impl Plugin for GdanskCssStubPlugin {
         }
 
         Ok(Some(HookResolveIdOutput::from_id(
-            Self::resolve_virtual_id(args.specifier, args.importer),
+            self.resolve_virtual_id(args.specifier, args.importer),
         )))
     }
 
     async fn load(&self, _ctx: SharedLoadPluginContext, args: &HookLoadArgs<'_>) -> HookLoadReturn {
+        if let Some(path) = args.id.strip_prefix(GDANSK_CSS_STUB_INLINE_PREFIX) {
+            if let Some(code) = self.inline_style_module(path) {
+                return Ok(Some(HookLoadOutput {
+                    code: code.into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let Some(path) = args.id.strip_prefix(GDANSK_CSS_STUB_MODULE_PREFIX) {
+            if let Some(json) = self.module_exports.get(Path::new(path)) {
+                return Ok(Some(HookLoadOutput {
+                    code: format!("export default {json};").into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
         if !args.id.starts_with(GDANSK_CSS_STUB_PREFIX) {
             return Ok(None);
         }
@@ -758,8 +1739,16 @@ impl Plugin for GdanskServerEntrypointPlugin {
 }
 
 #[cfg(not(test))]
-fn client_entrypoint_plugins(include_app_entrypoint_plugin: bool) -> Vec<SharedPluginable> {
-    let mut plugins: Vec<SharedPluginable> = vec![Arc::new(GdanskCssStubPlugin)];
+fn client_entrypoint_plugins(
+    include_app_entrypoint_plugin: bool,
+    module_exports: CssModuleExports,
+    inline_styles: Option<CssInlineStyles>,
+) -> Vec<SharedPluginable> {
+    let css_stub = match inline_styles {
+        Some(inline_styles) => GdanskCssStubPlugin::with_inline_styles(module_exports, inline_styles),
+        None => GdanskCssStubPlugin::new(module_exports),
+    };
+    let mut plugins: Vec<SharedPluginable> = vec![Arc::new(css_stub)];
     if include_app_entrypoint_plugin {
         plugins.push(Arc::new(GdanskAppEntrypointPlugin));
     }
@@ -767,14 +1756,73 @@ fn client_entrypoint_plugins(include_app_entrypoint_plugin: bool) -> Vec<SharedP
 }
 
 #[cfg(not(test))]
-fn server_entrypoint_plugins() -> Vec<SharedPluginable> {
+fn server_entrypoint_plugins(module_exports: CssModuleExports) -> Vec<SharedPluginable> {
     vec![
-        Arc::new(GdanskCssStubPlugin),
+        Arc::new(GdanskCssStubPlugin::new(module_exports)),
         Arc::new(GdanskRuntimeModulePlugin),
         Arc::new(GdanskServerEntrypointPlugin),
     ]
 }
 
+#[cfg(not(test))]
+#[derive(Debug, Default)]
+struct GdanskTestHarnessPlugin;
+
+#[cfg(not(test))]
+impl GdanskTestHarnessPlugin {
+    fn source_id(id: &str) -> Option<&str> {
+        id.strip_suffix(TEST_ENTRYPOINT_QUERY)
+    }
+
+    fn wrapper_source(source_id: &str) -> Option<String> {
+        test_harness_wrapper_source(source_id)
+    }
+}
+
+#[cfg(not(test))]
+impl Plugin for GdanskTestHarnessPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("gdansk:test-harness")
+    }
+
+    async fn resolve_id(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookResolveIdArgs<'_>,
+    ) -> HookResolveIdReturn {
+        if args.specifier.ends_with(TEST_ENTRYPOINT_QUERY) {
+            return Ok(Some(HookResolveIdOutput::from_id(args.specifier)));
+        }
+        Ok(None)
+    }
+
+    async fn load(&self, _ctx: SharedLoadPluginContext, args: &HookLoadArgs<'_>) -> HookLoadReturn {
+        let Some(source_id) = Self::source_id(args.id) else {
+            return Ok(None);
+        };
+        let Some(wrapper_source) = Self::wrapper_source(source_id) else {
+            return Ok(None);
+        };
+        Ok(Some(HookLoadOutput {
+            code: wrapper_source.into(),
+            ..Default::default()
+        }))
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::ResolveId | HookUsage::Load
+    }
+}
+
+#[cfg(not(test))]
+fn test_entrypoint_plugins(module_exports: CssModuleExports) -> Vec<SharedPluginable> {
+    vec![
+        Arc::new(GdanskCssStubPlugin::new(module_exports)),
+        Arc::new(GdanskRuntimeModulePlugin),
+        Arc::new(GdanskTestHarnessPlugin),
+    ]
+}
+
 #[cfg(not(test))]
 impl DevEngineCloseGuard {
     fn new(engine: Arc<DevEngine>) -> Self {
@@ -830,6 +1878,130 @@ fn is_supported_jsx_extension(path: &Path) -> bool {
         .is_some_and(|ext| ext.eq_ignore_ascii_case("tsx") || ext.eq_ignore_ascii_case("jsx"))
 }
 
+// Parsed import map: a set of global bare-specifier mappings, flattened into
+// rolldown's alias table. Only the global `imports` are supported — per-scope
+// overrides are not, because rolldown's `ResolveOptions.alias` is global and
+// has no notion of the importing module, so an importer-scoped mapping could
+// not be honoured. A map carrying `scopes` is rejected at parse time rather
+// than accepted and silently ignored (see [`ImportMap::from_json`]). Entries
+// are kept sorted by descending key length so longest-prefix aliases win.
+#[derive(Debug, Clone, Default)]
+struct ImportMap {
+    imports: Vec<(String, String)>,
+}
+
+impl ImportMap {
+    // Flatten the global imports into rolldown's `(find, [replacement])` alias
+    // shape.
+    fn alias_entries(&self) -> Vec<(String, Vec<String>)> {
+        self.imports
+            .iter()
+            .map(|(key, target)| (key.clone(), vec![target.clone()]))
+            .collect()
+    }
+}
+
+#[cfg(not(test))]
+impl ImportMap {
+    // Parse `{ "imports": {..} }` from a JSON value, sorting the mapping table by
+    // descending key length up front. An import map carrying `scopes` is
+    // rejected: rolldown's alias table is global and cannot apply
+    // importer-scoped mappings, so accepting them would silently produce a wrong
+    // build. Only bare-specifier global imports are supported.
+    fn from_json(value: &Value) -> Result<Self, BundleError> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| BundleError::validation("import map must be a JSON object"))?;
+
+        let imports = match object.get("imports") {
+            Some(imports) => parse_mapping(imports, "imports")?,
+            None => Vec::new(),
+        };
+
+        if object.contains_key("scopes") {
+            return Err(BundleError::validation(
+                "import map `scopes` are not supported: the bundler resolves aliases globally and cannot apply importer-scoped mappings; use the global `imports` table instead",
+            ));
+        }
+
+        Ok(Self { imports })
+    }
+
+    // Validate that every mapped target that looks like a local file resolves to
+    // an existing path inside `cwd`, reusing the canonicalize + `strip_prefix`
+    // guard that [`normalize_pages`] applies to page inputs. Bare and URL targets
+    // are left for the resolver/vendor pass to handle.
+    fn validate_targets(&self, cwd: &Path, cwd_canonical: &Path) -> Result<(), BundleError> {
+        for (key, target) in &self.imports {
+            if !is_local_target(target) {
+                continue;
+            }
+            let candidate = cwd.join(target);
+            let canonical = dunce::simplified(&candidate.canonicalize().map_err(|err| {
+                BundleError::validation(format!(
+                    "import map target for `{key}` does not resolve: {target} ({err})"
+                ))
+            })?)
+            .to_path_buf();
+            if canonical.strip_prefix(cwd_canonical).is_err() {
+                return Err(BundleError::validation(format!(
+                    "import map target for `{key}` must resolve inside cwd {}: {target}",
+                    cwd_canonical.display()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(test))]
+fn parse_mapping(value: &Value, label: &str) -> Result<Vec<(String, String)>, BundleError> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| BundleError::validation(format!("import map `{label}` must be an object")))?;
+    let mut entries: Vec<(String, String)> = object
+        .iter()
+        .map(|(key, target)| {
+            let target = target.as_str().ok_or_else(|| {
+                BundleError::validation(format!(
+                    "import map `{label}` target for `{key}` must be a string"
+                ))
+            })?;
+            Ok((key.clone(), target.to_string()))
+        })
+        .collect::<Result<_, BundleError>>()?;
+    entries.sort_by(|left, right| right.0.len().cmp(&left.0.len()));
+    Ok(entries)
+}
+
+#[cfg(not(test))]
+fn load_import_map(path: &Path, cwd: &Path) -> Result<ImportMap, BundleError> {
+    let resolved = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    };
+    let source = fs::read_to_string(&resolved).map_err(|err| {
+        BundleError::validation(format!("failed to read import map {}: {err}", path.display()))
+    })?;
+    let value: Value = deno_core::serde_json::from_str(&source).map_err(|err| {
+        BundleError::validation(format!("import map {} is not valid JSON: {err}", path.display()))
+    })?;
+    let import_map = ImportMap::from_json(&value)?;
+    import_map.validate_targets(cwd, cwd)?;
+    Ok(import_map)
+}
+
+// A target names a local file when it is a relative or absolute filesystem path
+// rather than a bare specifier (`react`) or a URL (`https://…`).
+fn is_local_target(target: &str) -> bool {
+    target.starts_with("./")
+        || target.starts_with("../")
+        || target.starts_with('/')
+        || target.starts_with(".\\")
+        || target.starts_with("..\\")
+}
+
 fn normalize_pages(
     pages: Vec<PageSpec>,
     cwd: &Path,
@@ -898,8 +2070,8 @@ fn normalize_pages(
             ))
         })?;
 
-        let import = normalize_relative_for_rolldown(relative_path, "input path")?;
-        let key = import.clone();
+        let import = Istr::new(&normalize_relative_for_rolldown(relative_path, "input path")?);
+        let key = import.to_string();
 
         if provided_page.ssr && !provided_page.app {
             return Err(BundleError::validation(format!(
@@ -962,7 +2134,8 @@ fn normalize_pages(
 
         let client_js_path = client_stem_path.with_extension("js");
         let client_css_path = client_stem_path.with_extension("css");
-        let client_name = normalize_relative_for_rolldown(&client_stem_path, "entry name")?;
+        let client_name =
+            Istr::new(&normalize_relative_for_rolldown(&client_stem_path, "entry name")?);
         let _ = normalize_relative_for_rolldown(&client_js_path, "client output path")?;
         let _ = normalize_relative_for_rolldown(&client_css_path, "client css output path")?;
 
@@ -988,10 +2161,10 @@ fn normalize_pages(
                 )));
             }
             let _ = normalize_relative_for_rolldown(&server_js_path, "server output path")?;
-            Some(normalize_relative_for_rolldown(
+            Some(Istr::new(&normalize_relative_for_rolldown(
                 &server_stem_path,
                 "server entry name",
-            )?)
+            )?))
         } else {
             None
         };
@@ -1007,7 +2180,7 @@ fn normalize_pages(
         });
     }
 
-    normalized_pages.sort_unstable_by(|left, right| left.import.cmp(&right.import));
+    normalized_pages.sort_unstable_by(|left, right| (*left.import).cmp(&*right.import));
     Ok(normalized_pages)
 }
 
@@ -1028,12 +2201,12 @@ fn parse_pages_from_python(py: Python<'_>, pages: Vec<Py<Page>>) -> Vec<PageSpec
 }
 
 #[cfg(not(test))]
-fn build_input_items(fields: Vec<(String, String)>) -> Vec<InputItem> {
+fn build_input_items(fields: Vec<(Istr, Istr)>) -> Vec<InputItem> {
     fields
         .into_iter()
         .map(|(name, import)| InputItem {
-            name: Some(name),
-            import,
+            name: Some(name.to_string()),
+            import: import.to_string(),
         })
         .collect()
 }
@@ -1047,17 +2220,22 @@ async fn run_bundler(
     dev: bool,
     format: Option<OutputFormat>,
     plugins: Vec<SharedPluginable>,
+    alias: Option<Vec<(String, Vec<String>)>>,
+    single_file: bool,
 ) -> Result<(), PyErr> {
     let mut options = BundlerOptions {
         input: Some(input_items),
         cwd: Some(cwd),
         dir: Some(output_dir_string),
         entry_filenames: Some("[name].js".to_string().into()),
-        asset_filenames: Some("[name].css".to_string().into()),
+        // Compile mode inlines styles into the entry JS, so no separate CSS
+        // asset is emitted.
+        asset_filenames: (!single_file).then(|| "[name].css".to_string().into()),
         minify: Some(minify.into()),
         format,
         resolve: Some(ResolveOptions {
             condition_names: Some(vec!["module".to_string(), "style".to_string()]),
+            alias,
             ..Default::default()
         }),
         ..Default::default()
@@ -1105,15 +2283,219 @@ async fn run_bundler(
     Ok(())
 }
 
+// Serialize a [`BuildEvent`] to a dict and hand it to the Python callback.
+#[cfg(not(test))]
+fn emit_build_event(callback: &Py<PyAny>, event: &BuildEvent) -> Result<(), PyErr> {
+    let value = deno_core::serde_json::to_value(event)
+        .map_err(|err| py_runtime_error("failed to serialize build event", err))?;
+    Python::attach(|py| {
+        let payload = json_value_to_py(py, &value)?;
+        callback.call1(py, (payload,)).map(|_| ())
+    })
+}
+
+// Convert a JSON value into the equivalent native Python object so callbacks
+// receive plain dicts/lists rather than serialized strings.
+#[cfg(not(test))]
+fn json_value_to_py<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    use pyo3::types::{PyDict, PyList};
+    match value {
+        Value::Null => Ok(py.None().into_bound(py)),
+        Value::Bool(flag) => Ok(flag.into_pyobject(py)?.to_owned().into_any()),
+        Value::Number(number) => {
+            if let Some(int) = number.as_u64() {
+                Ok(int.into_pyobject(py)?.into_any())
+            } else if let Some(int) = number.as_i64() {
+                Ok(int.into_pyobject(py)?.into_any())
+            } else {
+                Ok(number.as_f64().unwrap_or_default().into_pyobject(py)?.into_any())
+            }
+        }
+        Value::String(text) => Ok(text.into_pyobject(py)?.into_any()),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(json_value_to_py(py, item)?)?;
+            }
+            Ok(list.into_any())
+        }
+        Value::Object(entries) => {
+            let dict = PyDict::new(py);
+            for (key, item) in entries {
+                dict.set_item(key, json_value_to_py(py, item)?)?;
+            }
+            Ok(dict.into_any())
+        }
+    }
+}
+
+// Bundle a single entry, emitting its own start/done/failed events. Each entry
+// is built in isolation so `EntryFailed` names the exact page that broke with
+// its own error and `EntryDone` carries that page's own build duration and
+// output sizes — the per-entry progress reporting that is the point of the
+// streamed build. Returns `true` when the entry failed so the caller can count
+// partial failures without aborting the whole run. `output_root` must be the
+// absolute directory rolldown writes into so the reported sizes are read from
+// the right place regardless of the caller's `cwd`.
+#[cfg(not(test))]
+#[allow(clippy::too_many_arguments)]
+async fn bundle_streamed_entry(
+    callback: &Py<PyAny>,
+    kind: EntryKind,
+    item: InputItem,
+    cwd: PathBuf,
+    output_root: &Path,
+    output_dir_string: String,
+    minify: bool,
+    format: Option<OutputFormat>,
+    plugins: Vec<SharedPluginable>,
+    alias: Option<Vec<(String, Vec<String>)>>,
+    single_file: bool,
+) -> Result<bool, PyErr> {
+    let name = item.name.clone().unwrap_or_default();
+    emit_build_event(
+        callback,
+        &BuildEvent::EntryStart {
+            name: name.clone(),
+            kind,
+        },
+    )?;
+
+    let started = std::time::Instant::now();
+    let result = run_bundler(
+        vec![item],
+        cwd,
+        output_dir_string,
+        minify,
+        false,
+        format,
+        plugins,
+        alias,
+        single_file,
+    )
+    .await;
+
+    match result {
+        Ok(()) => {
+            let (bytes, css_bytes) = entry_output_sizes(output_root, &name);
+            emit_build_event(
+                callback,
+                &BuildEvent::EntryDone {
+                    name,
+                    duration_ms: started.elapsed().as_millis(),
+                    bytes,
+                    css_bytes,
+                },
+            )?;
+            Ok(false)
+        }
+        Err(err) => {
+            let message = Python::attach(|py| err.value(py).to_string());
+            emit_build_event(callback, &BuildEvent::EntryFailed { name, message })?;
+            Ok(true)
+        }
+    }
+}
+
+// Execute a freshly bundled test module and stream one `Result` event per case
+// it reports. Returns `true` when any case failed (or the module could not be
+// read or executed) so the caller can honour `fail_fast`. A module that
+// registers no cases still emits a single passing `Result`, keeping one
+// terminal event per planned entry.
 #[cfg(not(test))]
-#[pyfunction(signature = (pages, dev = false, minify = true, output = None, cwd = None))]
+async fn run_test_file(
+    output_root: &Path,
+    name: &str,
+    started: std::time::Instant,
+    tx: &tokio::sync::mpsc::UnboundedSender<TestEvent>,
+) -> Result<bool, PyErr> {
+    let path = output_root.join(format!("{name}.js"));
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(err) => {
+            let _ = tx.send(TestEvent::Result {
+                name: name.to_owned(),
+                duration_ms: started.elapsed().as_millis(),
+                outcome: TestOutcome::Failed(format!(
+                    "failed to read bundled test output {}: {err}",
+                    path.display()
+                )),
+            });
+            return Ok(true);
+        }
+    };
+
+    let name_owned = name.to_owned();
+    let execution = tokio::task::spawn_blocking(move || run_test_module(&name_owned, source))
+        .await
+        .map_err(|err| py_runtime_error("test execution task failed", err))?;
+
+    match execution {
+        Err(message) => {
+            let _ = tx.send(TestEvent::Result {
+                name: name.to_owned(),
+                duration_ms: started.elapsed().as_millis(),
+                outcome: TestOutcome::Failed(message),
+            });
+            Ok(true)
+        }
+        Ok(cases) if cases.is_empty() => {
+            let _ = tx.send(TestEvent::Result {
+                name: name.to_owned(),
+                duration_ms: started.elapsed().as_millis(),
+                outcome: TestOutcome::Ok,
+            });
+            Ok(false)
+        }
+        Ok(cases) => {
+            let mut any_failed = false;
+            for case in cases {
+                let outcome = match case.status.as_str() {
+                    "ok" => TestOutcome::Ok,
+                    "ignored" => TestOutcome::Ignored,
+                    _ => {
+                        any_failed = true;
+                        TestOutcome::Failed(case.message.unwrap_or_default())
+                    }
+                };
+                let _ = tx.send(TestEvent::Result {
+                    name: case.name,
+                    duration_ms: case.duration_ms,
+                    outcome,
+                });
+            }
+            Ok(any_failed)
+        }
+    }
+}
+
+// Read the emitted `.js` and `.css` byte sizes for an entry, treating a missing
+// file as zero bytes (a client entry need not produce any CSS). `output_root`
+// must be the absolute directory rolldown writes into, not the caller-relative
+// output path.
+#[cfg(not(test))]
+fn entry_output_sizes(output_root: &Path, name: &str) -> (u64, u64) {
+    let size = |extension: &str| {
+        fs::metadata(output_root.join(format!("{name}.{extension}")))
+            .map(|meta| meta.len())
+            .unwrap_or(0)
+    };
+    (size("js"), size("css"))
+}
+
+#[cfg(not(test))]
+#[pyfunction(signature = (pages, dev = false, minify = true, sourcemaps = false, single_file = false, output = None, cwd = None, import_map = None, on_event = None))]
 pub(crate) fn bundle(
     py: Python<'_>,
     pages: Vec<Py<Page>>,
     dev: bool,
     minify: bool,
+    sourcemaps: bool,
+    single_file: bool,
     output: Option<PathBuf>,
     cwd: Option<PathBuf>,
+    import_map: Option<PathBuf>,
+    on_event: Option<Py<PyAny>>,
 ) -> PyResult<Bound<'_, PyAny>> {
     let parsed_pages = parse_pages_from_python(py, pages);
 
@@ -1130,15 +2512,41 @@ pub(crate) fn bundle(
         let output_dir = output.unwrap_or_else(|| PathBuf::from(".gdansk"));
         let output_dir_string =
             path_to_utf8(&output_dir, "output path").map_err(map_bundle_error)?;
+        let import_map = match import_map {
+            Some(path) => Some(load_import_map(&path, &cwd).map_err(map_bundle_error)?),
+            None => None,
+        };
+        let alias = import_map.as_ref().map(ImportMap::alias_entries);
         let normalized =
             normalize_pages(parsed_pages, &cwd, &output_dir).map_err(map_bundle_error)?;
-        build_css_outputs(&normalized, &cwd, &output_dir, minify).map_err(map_bundle_error)?;
+        let (exports_map, css_graph, inline_css) = build_css_outputs(
+            &normalized,
+            &cwd,
+            &output_dir,
+            minify,
+            sourcemaps,
+            Targets::default(),
+            single_file,
+        )
+        .map_err(map_bundle_error)?;
+        let module_exports: CssModuleExports = Arc::new(exports_map.clone());
+        let inline_styles: Option<CssInlineStyles> =
+            single_file.then(|| Arc::new(inline_css));
 
         let client_items = build_input_items(build_client_input_item_fields(&normalized));
         let server_items = build_input_items(build_server_input_item_fields(&normalized));
         let has_app_entries = normalized.iter().any(|page| page.app);
 
         if dev {
+            spawn_css_dev_watcher(
+                normalized.clone(),
+                cwd.clone(),
+                output_dir.clone(),
+                minify,
+                sourcemaps,
+                css_graph,
+                exports_map,
+            );
             if server_items.is_empty() {
                 run_bundler(
                     client_items,
@@ -1147,7 +2555,9 @@ pub(crate) fn bundle(
                     minify,
                     dev,
                     None,
-                    client_entrypoint_plugins(has_app_entries),
+                    client_entrypoint_plugins(has_app_entries, module_exports.clone(), inline_styles.clone()),
+                    alias.clone(),
+                    single_file,
                 )
                 .await?;
             } else {
@@ -1159,7 +2569,9 @@ pub(crate) fn bundle(
                         minify,
                         dev,
                         None,
-                        client_entrypoint_plugins(has_app_entries),
+                        client_entrypoint_plugins(has_app_entries, module_exports.clone(), inline_styles.clone()),
+                        alias.clone(),
+                        single_file,
                     ),
                     run_bundler(
                         server_items,
@@ -1168,13 +2580,73 @@ pub(crate) fn bundle(
                         minify,
                         dev,
                         Some(OutputFormat::Iife),
-                        server_entrypoint_plugins(),
+                        server_entrypoint_plugins(module_exports.clone()),
+                        alias.clone(),
+                        false,
                     ),
                 )?;
             }
             return Python::attach(|py| Ok(py.None()));
         }
 
+        if let Some(callback) = on_event {
+            let has_server = !server_items.is_empty();
+            emit_build_event(
+                &callback,
+                &BuildEvent::Plan {
+                    total_entries: client_items.len() + server_items.len(),
+                    has_server,
+                },
+            )?;
+
+            let started = std::time::Instant::now();
+            let output_root = css_output_root(&cwd, &output_dir);
+            let mut failed = 0usize;
+            for item in client_items {
+                let entry_failed = bundle_streamed_entry(
+                    &callback,
+                    EntryKind::Client,
+                    item,
+                    cwd.clone(),
+                    &output_root,
+                    output_dir_string.clone(),
+                    minify,
+                    None,
+                    client_entrypoint_plugins(has_app_entries, module_exports.clone(), inline_styles.clone()),
+                    alias.clone(),
+                    single_file,
+                )
+                .await?;
+                failed += usize::from(entry_failed);
+            }
+            for item in server_items {
+                let entry_failed = bundle_streamed_entry(
+                    &callback,
+                    EntryKind::Server,
+                    item,
+                    cwd.clone(),
+                    &output_root,
+                    output_dir_string.clone(),
+                    minify,
+                    Some(OutputFormat::Iife),
+                    server_entrypoint_plugins(module_exports.clone()),
+                    alias.clone(),
+                    false,
+                )
+                .await?;
+                failed += usize::from(entry_failed);
+            }
+
+            emit_build_event(
+                &callback,
+                &BuildEvent::Complete {
+                    duration_ms: started.elapsed().as_millis(),
+                    failed,
+                },
+            )?;
+            return Python::attach(|py| Ok(py.None()));
+        }
+
         run_bundler(
             client_items,
             cwd.clone(),
@@ -1182,7 +2654,9 @@ pub(crate) fn bundle(
             minify,
             dev,
             None,
-            client_entrypoint_plugins(has_app_entries),
+            client_entrypoint_plugins(has_app_entries, module_exports.clone(), inline_styles.clone()),
+            alias.clone(),
+            single_file,
         )
         .await?;
         if !server_items.is_empty() {
@@ -1193,7 +2667,9 @@ pub(crate) fn bundle(
                 minify,
                 dev,
                 Some(OutputFormat::Iife),
-                server_entrypoint_plugins(),
+                server_entrypoint_plugins(module_exports.clone()),
+                alias.clone(),
+                false,
             )
             .await?;
         }
@@ -1201,6 +2677,321 @@ pub(crate) fn bundle(
     })
 }
 
+#[cfg(not(test))]
+fn build_vendor_plan(
+    normalized: &[NormalizedPage],
+    cwd: &Path,
+) -> Result<VendorPlan, BundleError> {
+    let mut visited = HashSet::new();
+    let mut plan = VendorPlan::default();
+    for page in normalized {
+        collect_vendor_plan(&page.absolute_path, cwd, &mut visited, &mut plan)?;
+    }
+    plan.imports.sort_by(|left, right| left.0.cmp(&right.0));
+    Ok(plan)
+}
+
+// Copy every planned dependency into `<output>/vendor/` preserving its layout,
+// then emit `vendor/import_map.json` pinning each bare specifier to its
+// vendored copy so later `bundle` calls can resolve offline.
+#[cfg(not(test))]
+fn write_vendor_output(plan: &VendorPlan, output_dir: &Path) -> Result<(), BundleError> {
+    let vendor_dir = output_dir.join("vendor");
+    for (source, vendor_relative) in &plan.files {
+        let destination = vendor_dir.join(vendor_relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).map_err(|err| {
+                BundleError::runtime(format!(
+                    "failed to create vendor directory {}: {err}",
+                    parent.display()
+                ))
+            })?;
+        }
+        fs::copy(source, &destination).map_err(|err| {
+            BundleError::runtime(format!(
+                "failed to vendor {} -> {}: {err}",
+                source.display(),
+                destination.display()
+            ))
+        })?;
+    }
+
+    let mut imports = deno_core::serde_json::Map::new();
+    for (specifier, vendor_relative) in &plan.imports {
+        imports.insert(
+            specifier.clone(),
+            Value::String(format!("./vendor/{vendor_relative}")),
+        );
+    }
+    let document = Value::Object(deno_core::serde_json::Map::from_iter([(
+        "imports".to_string(),
+        Value::Object(imports),
+    )]));
+    let serialized = deno_core::serde_json::to_string_pretty(&document).map_err(|err| {
+        BundleError::runtime(format!("failed to serialize vendor import map: {err}"))
+    })?;
+
+    fs::create_dir_all(&vendor_dir).map_err(|err| {
+        BundleError::runtime(format!(
+            "failed to create vendor directory {}: {err}",
+            vendor_dir.display()
+        ))
+    })?;
+    fs::write(vendor_dir.join("import_map.json"), serialized).map_err(|err| {
+        BundleError::runtime(format!("failed to write vendor import map: {err}"))
+    })?;
+    Ok(())
+}
+
+#[cfg(not(test))]
+#[pyfunction(signature = (pages, output = None, cwd = None))]
+pub(crate) fn vendor(
+    py: Python<'_>,
+    pages: Vec<Py<Page>>,
+    output: Option<PathBuf>,
+    cwd: Option<PathBuf>,
+) -> PyResult<Bound<'_, PyAny>> {
+    let parsed_pages = parse_pages_from_python(py, pages);
+
+    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let cwd = match cwd {
+            Some(dir) => dunce::simplified(
+                &dir.canonicalize()
+                    .map_err(|err| py_runtime_error("failed to resolve provided cwd", err))?,
+            )
+            .to_path_buf(),
+            None => std::env::current_dir()
+                .map_err(|err| py_runtime_error("failed to read current working directory", err))?,
+        };
+        let output_dir = output.unwrap_or_else(|| PathBuf::from(".gdansk"));
+        let normalized =
+            normalize_pages(parsed_pages, &cwd, &output_dir).map_err(map_bundle_error)?;
+        let plan = build_vendor_plan(&normalized, &cwd).map_err(map_bundle_error)?;
+        write_vendor_output(&plan, &output_dir).map_err(map_bundle_error)?;
+        Python::attach(|py| Ok(py.None()))
+    })
+}
+
+// A discovered test entry, carrying the rolldown import specifier and the entry
+// name used for the bundled artifact.
+#[cfg(not(test))]
+struct TestEntry {
+    import: String,
+    name: String,
+}
+
+// `true` for files that sit next to a page and follow the `*.test.tsx` /
+// `*.test.jsx` naming convention the runner discovers.
+fn is_test_file(file_name: &str) -> bool {
+    file_name.ends_with(".test.tsx") || file_name.ends_with(".test.jsx")
+}
+
+// Scan each page's directory for sibling `*.test.{tsx,jsx}` files, returning the
+// canonical paths with duplicates removed so two pages in the same directory do
+// not double-count shared tests.
+#[cfg(not(test))]
+fn discover_test_files(pages: &[PageSpec], cwd: &Path) -> Result<Vec<PathBuf>, BundleError> {
+    let mut discovered = Vec::new();
+    let mut seen = HashSet::new();
+    for page in pages {
+        let absolute = if page.path.is_absolute() {
+            page.path.clone()
+        } else {
+            cwd.join(&page.path)
+        };
+        let Some(directory) = absolute.parent() else {
+            continue;
+        };
+        let entries = fs::read_dir(directory).map_err(|err| {
+            BundleError::runtime(format!(
+                "failed to read test directory {}: {err}",
+                directory.display()
+            ))
+        })?;
+        for entry in entries {
+            let path = entry
+                .map_err(|err| {
+                    BundleError::runtime(format!(
+                        "failed to read test directory {}: {err}",
+                        directory.display()
+                    ))
+                })?
+                .path();
+            let is_test = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(is_test_file);
+            if !is_test {
+                continue;
+            }
+            let path = dunce::simplified(&path.canonicalize().map_err(|err| {
+                BundleError::runtime(format!("failed to canonicalize test {}: {err}", path.display()))
+            })?)
+            .to_path_buf();
+            if seen.insert(path.clone()) {
+                discovered.push(path);
+            }
+        }
+    }
+    discovered.sort();
+    Ok(discovered)
+}
+
+// Validate each discovered test file with the same canonicalize + `strip_prefix`
+// guard [`normalize_pages`] applies, but without the `page.tsx` naming rule, and
+// derive its import specifier and entry name.
+#[cfg(not(test))]
+fn normalize_test_entries(
+    files: Vec<PathBuf>,
+    cwd: &Path,
+) -> Result<Vec<TestEntry>, BundleError> {
+    let cwd_canonical = dunce::simplified(&cwd.canonicalize().map_err(|err| {
+        BundleError::runtime(format!(
+            "failed to resolve current working directory {}: {err}",
+            cwd.display()
+        ))
+    })?)
+    .to_path_buf();
+
+    let mut entries = Vec::with_capacity(files.len());
+    for file in files {
+        let canonical = dunce::simplified(&file.canonicalize().map_err(|err| {
+            BundleError::runtime(format!("failed to canonicalize test {}: {err}", file.display()))
+        })?)
+        .to_path_buf();
+        let relative = canonical.strip_prefix(&cwd_canonical).map_err(|_| {
+            BundleError::validation(format!(
+                "test path must resolve inside cwd {}: {}",
+                cwd_canonical.display(),
+                canonical.display()
+            ))
+        })?;
+        entries.push(TestEntry {
+            import: normalize_relative_for_rolldown(relative, "test path")?,
+            name: normalize_relative_for_rolldown(&relative.with_extension(""), "test name")?,
+        });
+    }
+    entries.sort_by(|left, right| left.import.cmp(&right.import));
+    Ok(entries)
+}
+
+// Serialize a [`TestEvent`] to a dict and hand it to the Python callback.
+#[cfg(not(test))]
+fn forward_test_event(callback: &Py<PyAny>, event: &TestEvent) -> Result<(), PyErr> {
+    let value = deno_core::serde_json::to_value(event)
+        .map_err(|err| py_runtime_error("failed to serialize test event", err))?;
+    Python::attach(|py| {
+        let payload = json_value_to_py(py, &value)?;
+        callback.call1(py, (payload,)).map(|_| ())
+    })
+}
+
+#[cfg(not(test))]
+#[pyfunction(signature = (pages, filter = None, fail_fast = false, output = None, cwd = None, on_event = None))]
+pub(crate) fn test(
+    py: Python<'_>,
+    pages: Vec<Py<Page>>,
+    filter: Option<String>,
+    fail_fast: bool,
+    output: Option<PathBuf>,
+    cwd: Option<PathBuf>,
+    on_event: Option<Py<PyAny>>,
+) -> PyResult<Bound<'_, PyAny>> {
+    let parsed_pages = parse_pages_from_python(py, pages);
+
+    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let cwd = match cwd {
+            Some(dir) => dunce::simplified(
+                &dir.canonicalize()
+                    .map_err(|err| py_runtime_error("failed to resolve provided cwd", err))?,
+            )
+            .to_path_buf(),
+            None => std::env::current_dir()
+                .map_err(|err| py_runtime_error("failed to read current working directory", err))?,
+        };
+        let output_dir = output.unwrap_or_else(|| PathBuf::from(".gdansk"));
+        let output_dir_string =
+            path_to_utf8(&output_dir, "output path").map_err(map_bundle_error)?;
+
+        let discovered = discover_test_files(&parsed_pages, &cwd).map_err(map_bundle_error)?;
+        let all_entries = normalize_test_entries(discovered, &cwd).map_err(map_bundle_error)?;
+
+        let (kept, filtered): (Vec<TestEntry>, Vec<TestEntry>) =
+            all_entries.into_iter().partition(|entry| match &filter {
+                Some(needle) => entry.name.contains(needle.as_str()),
+                None => true,
+            });
+
+        // Events are produced into an mpsc channel and drained to the callback as
+        // they arrive, keeping progress streaming rather than batched at the end.
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<TestEvent>();
+        let drain = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<TestEvent>| -> Result<(), PyErr> {
+            while let Ok(event) = rx.try_recv() {
+                if let Some(callback) = &on_event {
+                    forward_test_event(callback, &event)?;
+                }
+            }
+            Ok(())
+        };
+
+        let _ = tx.send(TestEvent::Plan {
+            pending: kept.len(),
+            filtered: filtered.len(),
+        });
+        drain(&mut rx)?;
+
+        let module_exports: CssModuleExports = Arc::new(HashMap::new());
+        let output_root = css_output_root(&cwd, &output_dir);
+        for entry in kept {
+            let _ = tx.send(TestEvent::Wait {
+                name: entry.name.clone(),
+            });
+            drain(&mut rx)?;
+
+            let item = InputItem {
+                name: Some(entry.name.clone()),
+                import: format!("{}{TEST_ENTRYPOINT_QUERY}", entry.import),
+            };
+            let started = std::time::Instant::now();
+            let bundled = run_bundler(
+                vec![item],
+                cwd.clone(),
+                output_dir_string.clone(),
+                true,
+                false,
+                Some(OutputFormat::Iife),
+                test_entrypoint_plugins(module_exports.clone()),
+                None,
+                false,
+            )
+            .await;
+
+            // A bundling failure fails the whole file; otherwise the emitted
+            // module is executed and each case it reports becomes its own
+            // `Result` event, mirroring Deno's per-test reporting.
+            let failed = match bundled {
+                Err(err) => {
+                    let message = Python::attach(|py| err.value(py).to_string());
+                    let _ = tx.send(TestEvent::Result {
+                        name: entry.name.clone(),
+                        duration_ms: started.elapsed().as_millis(),
+                        outcome: TestOutcome::Failed(message),
+                    });
+                    true
+                }
+                Ok(()) => run_test_file(&output_root, &entry.name, started, &tx).await?,
+            };
+            drain(&mut rx)?;
+
+            if failed && fail_fast {
+                break;
+            }
+        }
+
+        Python::attach(|py| Ok(py.None()))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -1423,57 +3214,6 @@ mod tests {
         assert_eq!(fields.get("main"), Some(&"main.tsx".to_string()));
     }
 
-    #[test]
-    fn css_scanner_detects_side_effect_imports() {
-        let imports = collect_direct_css_imports(
-            r#"
-import "./page.css";
-export const page = 1;
-"#,
-        );
-
-        assert_eq!(imports, vec!["./page.css".to_string()]);
-    }
-
-    #[test]
-    fn css_scanner_preserves_import_order() {
-        let imports = collect_direct_css_imports(
-            r#"
-import "./first.css";
-import "./second.css";
-"#,
-        );
-
-        assert_eq!(
-            imports,
-            vec!["./first.css".to_string(), "./second.css".to_string()]
-        );
-    }
-
-    #[test]
-    fn css_scanner_ignores_non_css_imports() {
-        let imports = collect_direct_css_imports(
-            r#"
-import "./page.js";
-import value from "./other.ts";
-"#,
-        );
-
-        assert!(imports.is_empty());
-    }
-
-    #[test]
-    fn css_scanner_ignores_dynamic_imports() {
-        let imports = collect_direct_css_imports(
-            r#"
-await import("./page.css");
-const loader = () => import("./other.css");
-"#,
-        );
-
-        assert!(imports.is_empty());
-    }
-
     #[test]
     fn server_input_fields_include_only_ssr_views() {
         let project = TempProject::new();
@@ -1518,4 +3258,58 @@ const loader = () => import("./other.css");
             .expect("expected server wrapper");
         assert!(!wrapper.contains("globalThis.__gdansk_html"));
     }
+
+    fn import_map(imports: &[(&str, &str)]) -> ImportMap {
+        let mut entries: Vec<(String, String)> = imports
+            .iter()
+            .map(|(key, target)| (key.to_string(), target.to_string()))
+            .collect();
+        entries.sort_by(|left, right| right.0.len().cmp(&left.0.len()));
+        ImportMap { imports: entries }
+    }
+
+    #[test]
+    fn vendored_relative_mirrors_path_below_node_modules() {
+        let resolved = PathBuf::from("/proj/node_modules/@ui/button/index.js");
+        assert_eq!(
+            vendored_relative(&resolved),
+            Some("@ui/button/index.js".to_string())
+        );
+    }
+
+    #[test]
+    fn vendored_relative_uses_last_node_modules() {
+        let resolved = PathBuf::from("/proj/node_modules/a/node_modules/b/index.js");
+        assert_eq!(vendored_relative(&resolved), Some("b/index.js".to_string()));
+    }
+
+    #[test]
+    fn vendored_relative_is_none_for_local_files() {
+        assert_eq!(vendored_relative(&PathBuf::from("/proj/src/app.tsx")), None);
+    }
+
+    #[test]
+    fn test_files_are_recognised_by_suffix() {
+        assert!(is_test_file("home.test.tsx"));
+        assert!(is_test_file("button.test.jsx"));
+        assert!(!is_test_file("page.tsx"));
+        assert!(!is_test_file("testing.tsx"));
+    }
+
+    #[test]
+    fn remote_specifiers_are_recognised() {
+        assert!(is_remote_specifier("https://esm.sh/react"));
+        assert!(is_remote_specifier("npm:react"));
+        assert!(!is_remote_specifier("react"));
+        assert!(!is_remote_specifier("./local"));
+    }
+
+    #[test]
+    fn import_map_alias_entries_flatten_global_imports() {
+        let map = import_map(&[("@ui/", "./src/ui/")]);
+        assert_eq!(
+            map.alias_entries(),
+            vec![("@ui/".to_string(), vec!["./src/ui/".to_string()])]
+        );
+    }
 }
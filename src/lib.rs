@@ -1,29 +1,205 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     path::{Path, PathBuf},
 };
 
+use walkdir::WalkDir;
+
 #[cfg(not(test))]
 use pyo3::{
     exceptions::{PyRuntimeError, PyValueError},
     prelude::*,
+    types::PyDict,
 };
 #[cfg(not(test))]
-use rolldown::{Bundler, BundlerOptions, ExperimentalOptions, InputItem};
+use notify::{RecursiveMode, Watcher};
 #[cfg(not(test))]
-use rolldown_dev::{BundlerConfig, DevEngine, DevOptions, RebuildStrategy};
+use rolldown::{Bundler, BundlerOptions, ExperimentalOptions, InputItem, Output};
+
+// Cheaply-clonable reference-counted string. Cloning bumps an `Arc` refcount
+// instead of reallocating, so the `import`/`name`/collision-key strings that
+// flow through `normalize_inputs` are each allocated once and shared across the
+// collision map and the sort; `InputItem` construction still copies them into
+// owned `String`s. A single newtype also gives one place to later swap the
+// backing representation.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct RcStr(std::sync::Arc<str>);
+
+impl std::ops::Deref for RcStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::borrow::Borrow<str> for RcStr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for RcStr {
+    fn from(value: &str) -> Self {
+        Self(std::sync::Arc::from(value))
+    }
+}
+
+impl From<String> for RcStr {
+    fn from(value: String) -> Self {
+        Self(std::sync::Arc::from(value.as_str()))
+    }
+}
+
+impl PartialEq<str> for RcStr {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for RcStr {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl fmt::Debug for RcStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for RcStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 #[cfg(not(test))]
-use std::sync::Arc;
+impl deno_core::serde::Serialize for RcStr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: deno_core::serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(not(test))]
+impl<'de> deno_core::serde::Deserialize<'de> for RcStr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: deno_core::serde::Deserializer<'de>,
+    {
+        let value = <String as deno_core::serde::Deserialize>::deserialize(deserializer)?;
+        Ok(Self::from(value))
+    }
+}
 
 #[derive(Debug, Clone)]
 struct NormalizedInput {
-    import: String,
-    name: String,
+    import: RcStr,
+    name: RcStr,
     #[cfg_attr(not(test), allow(dead_code))]
     output_relative_js: PathBuf,
 }
 
+// Per-entry record of the absolute module paths that feed into each output,
+// keyed by the entry's `import`. Rebuilt or patched after every successful
+// compile so a dev-mode change notification can be mapped back to exactly the
+// entries it reaches.
+#[derive(Debug, Default)]
+struct DependencyGraph {
+    deps: HashMap<RcStr, HashSet<PathBuf>>,
+}
+
+impl DependencyGraph {
+    fn record(&mut self, entry: RcStr, files: HashSet<PathBuf>) {
+        self.deps.insert(entry, files);
+    }
+
+    // Entries whose dependency set contains at least one changed file.
+    fn affected(&self, changed: &HashSet<PathBuf>) -> HashSet<RcStr> {
+        self.deps
+            .iter()
+            .filter(|(_, files)| !files.is_disjoint(changed))
+            .map(|(entry, _)| entry.clone())
+            .collect()
+    }
+
+    // Every file currently feeding into any recorded entry.
+    fn known_files(&self) -> HashSet<PathBuf> {
+        self.deps.values().flatten().cloned().collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RebuildPlan {
+    Full,
+    Partial(HashSet<RcStr>),
+}
+
+// Decide what to rebuild for a dev-mode change notification. A changed path that
+// is not part of any recorded dependency set is treated as a newly-created or
+// deleted module and forces a full graph re-resolution; otherwise only the
+// entries whose subgraph intersects the change are rebuilt.
+fn plan_rebuild(graph: &DependencyGraph, changed: &HashSet<PathBuf>) -> RebuildPlan {
+    let known = graph.known_files();
+    if changed.iter().any(|path| !known.contains(path)) {
+        return RebuildPlan::Full;
+    }
+    RebuildPlan::Partial(graph.affected(changed))
+}
+
+// One build-manifest record: the entry name plus the fingerprinted output files
+// a server can serve for it. `css` is absent when the entry emits no stylesheet.
+#[cfg_attr(not(test), derive(deno_core::serde::Serialize))]
+#[cfg_attr(not(test), serde(crate = "deno_core::serde"))]
+#[derive(Debug, PartialEq, Eq)]
+struct ManifestEntry {
+    name: String,
+    js: String,
+    css: Option<String>,
+}
+
+// True when `filename` is the CSS output for the entry named `name`, matching
+// both the plain `[name].css` and the fingerprinted `[name].[hash].css` forms.
+fn is_entry_css(filename: &str, name: &str) -> bool {
+    filename.ends_with(".css")
+        && (filename == format!("{name}.css") || filename.starts_with(&format!("{name}.")))
+}
+
+// Assemble the entry→output mapping keyed by each input's original `import`, so
+// a web framework can resolve `home/page.tsx` to its fingerprinted outputs at
+// request time. Entries whose JS output is missing from `entry_js` are skipped.
+fn build_manifest(
+    normalized: &[NormalizedInput],
+    entry_js: &HashMap<String, String>,
+    css_files: &[String],
+) -> BTreeMap<String, ManifestEntry> {
+    let mut manifest = BTreeMap::new();
+    for input in normalized {
+        let name = input.name.to_string();
+        let Some(js) = entry_js.get(&name) else {
+            continue;
+        };
+        let css = css_files
+            .iter()
+            .find(|filename| is_entry_css(filename, &name))
+            .cloned();
+        manifest.insert(
+            input.import.to_string(),
+            ManifestEntry {
+                name,
+                js: js.clone(),
+                css,
+            },
+        );
+    }
+    manifest
+}
+
 #[derive(Debug, Clone)]
 enum BundleError {
     Validation(String),
@@ -48,41 +224,6 @@ impl fmt::Display for BundleError {
     }
 }
 
-#[cfg(not(test))]
-struct DevEngineCloseGuard {
-    engine: Option<Arc<DevEngine>>,
-}
-
-#[cfg(not(test))]
-impl DevEngineCloseGuard {
-    fn new(engine: Arc<DevEngine>) -> Self {
-        Self {
-            engine: Some(engine),
-        }
-    }
-
-    fn disarm(&mut self) {
-        self.engine = None;
-    }
-}
-
-#[cfg(not(test))]
-impl Drop for DevEngineCloseGuard {
-    fn drop(&mut self) {
-        let Some(engine) = self.engine.take() else {
-            return;
-        };
-
-        let Ok(handle) = tokio::runtime::Handle::try_current() else {
-            return;
-        };
-
-        handle.spawn(async move {
-            let _ = engine.close().await;
-        });
-    }
-}
-
 #[cfg(not(test))]
 fn py_runtime_error(context: &str, err: impl std::fmt::Display) -> PyErr {
     PyRuntimeError::new_err(format!("{context}: {err}"))
@@ -108,6 +249,89 @@ fn is_supported_jsx_extension(path: &Path) -> bool {
         .is_some_and(|ext| ext.eq_ignore_ascii_case("tsx") || ext.eq_ignore_ascii_case("jsx"))
 }
 
+// A provided input that carries glob metacharacters is expanded as a pattern
+// rather than treated as a literal path.
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|value| value.contains(['*', '?', '[', '{']))
+}
+
+// Expand glob patterns and directory roots into the concrete set of supported
+// entry files before normalization, so callers can pass `src/**/*.tsx` or a
+// bare `pages/` directory instead of enumerating every file. Literal file paths
+// are kept unless they match an `ignore` pattern (their existence and extension
+// are still checked by `normalize_inputs`); expanded entries are filtered to
+// supported extensions and likewise dropped when they match any `ignore`
+// pattern.
+fn expand_input_paths(
+    paths: HashSet<PathBuf>,
+    cwd: &Path,
+    ignore: &[String],
+) -> Result<HashSet<PathBuf>, BundleError> {
+    let ignore_patterns = ignore
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern).map_err(|err| {
+                BundleError::validation(format!("invalid ignore pattern {pattern}: {err}"))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let is_ignored = |path: &Path| {
+        let relative = path.strip_prefix(cwd).unwrap_or(path);
+        ignore_patterns
+            .iter()
+            .any(|pattern| pattern.matches_path(relative) || pattern.matches_path(path))
+    };
+
+    let mut collected = HashSet::new();
+    for provided in paths {
+        if is_glob_pattern(&provided) {
+            let pattern = if provided.is_absolute() {
+                provided.clone()
+            } else {
+                cwd.join(&provided)
+            };
+            let pattern = path_to_utf8(&pattern, "input glob")?;
+            let matches = glob::glob(&pattern).map_err(|err| {
+                BundleError::validation(format!("invalid input glob {pattern}: {err}"))
+            })?;
+            for entry in matches {
+                let path = entry.map_err(|err| {
+                    BundleError::runtime(format!("failed to expand input glob {pattern}: {err}"))
+                })?;
+                if path.is_file() && is_supported_jsx_extension(&path) && !is_ignored(&path) {
+                    collected.insert(path);
+                }
+            }
+        } else {
+            let absolute = if provided.is_absolute() {
+                provided.clone()
+            } else {
+                cwd.join(&provided)
+            };
+            if absolute.is_dir() {
+                for entry in WalkDir::new(&absolute) {
+                    let entry = entry.map_err(|err| {
+                        BundleError::runtime(format!(
+                            "failed to walk input directory {}: {err}",
+                            absolute.display()
+                        ))
+                    })?;
+                    let path = entry.path();
+                    if path.is_file() && is_supported_jsx_extension(path) && !is_ignored(path) {
+                        collected.insert(path.to_path_buf());
+                    }
+                }
+            } else if !is_ignored(&absolute) {
+                collected.insert(provided);
+            }
+        }
+    }
+
+    Ok(collected)
+}
+
 fn normalize_inputs(
     paths: HashSet<PathBuf>,
     cwd: &Path,
@@ -128,7 +352,7 @@ fn normalize_inputs(
     .to_path_buf();
 
     let mut normalized_inputs = Vec::with_capacity(paths.len());
-    let mut output_collisions: HashMap<PathBuf, String> = HashMap::new();
+    let mut output_collisions: HashMap<PathBuf, RcStr> = HashMap::new();
 
     for provided_path in paths {
         let absolute_candidate = if provided_path.is_absolute() {
@@ -178,8 +402,9 @@ fn normalize_inputs(
         let relative_without_ext = relative_path.with_extension("");
         let output_relative_js = relative_without_ext.with_extension("js");
 
-        let import = normalize_relative_for_rolldown(relative_path, "input path")?;
-        let name = normalize_relative_for_rolldown(&relative_without_ext, "entry name")?;
+        let import = RcStr::from(normalize_relative_for_rolldown(relative_path, "input path")?);
+        let name =
+            RcStr::from(normalize_relative_for_rolldown(&relative_without_ext, "entry name")?);
 
         if let Some(previous_input) =
             output_collisions.insert(output_relative_js.clone(), import.clone())
@@ -199,10 +424,144 @@ fn normalize_inputs(
         });
     }
 
-    normalized_inputs.sort_unstable_by(|left, right| left.import.cmp(&right.import));
+    normalized_inputs.sort_unstable_by(|left, right| (*left.import).cmp(&*right.import));
     Ok(normalized_inputs)
 }
 
+// The result of compiling a single entry: the absolute module paths that fed
+// into it (to route later changes back through the dependency graph) and the
+// output filenames the bundler emitted.
+#[cfg(not(test))]
+struct EntryBuild {
+    watch_files: HashSet<PathBuf>,
+    outputs: Vec<String>,
+}
+
+// Encode a caller-supplied define value as a compile-time constant. The value
+// must parse as JSON (a string, boolean or number, or any other JSON value such
+// as an object, array or null), whose serialized form is also its JS source;
+// text that is not valid JSON is rejected.
+#[cfg(not(test))]
+fn json_define_literal(value: &str) -> Option<String> {
+    deno_core::serde_json::from_str::<deno_core::serde_json::Value>(value)
+        .ok()
+        .map(|parsed| parsed.to_string())
+}
+
+// A JSON string literal for `value`, used when folding env-var snapshots into
+// the define map.
+#[cfg(not(test))]
+fn json_string_literal(value: &str) -> String {
+    deno_core::serde_json::Value::String(value.to_string()).to_string()
+}
+
+// Build the compile-time substitution map: explicit `define` entries (each a
+// JSON literal) take precedence, then env vars matching `env_prefix` are folded
+// in under `process.env.<NAME>`, and in dev mode `process.env.NODE_ENV` defaults
+// to `development` unless the caller already set it.
+#[cfg(not(test))]
+fn build_defines(
+    define: HashMap<String, String>,
+    env_prefix: Option<&str>,
+    env_vars: &[(String, String)],
+    dev: bool,
+) -> Result<BTreeMap<String, String>, BundleError> {
+    let mut defines = BTreeMap::new();
+
+    for (key, value) in define {
+        let literal = json_define_literal(&value).ok_or_else(|| {
+            BundleError::validation(format!(
+                "define value for `{key}` must be a JSON literal: {value}"
+            ))
+        })?;
+        defines.insert(key, literal);
+    }
+
+    if let Some(prefix) = env_prefix {
+        for (name, value) in env_vars {
+            if name.starts_with(prefix) {
+                defines
+                    .entry(format!("process.env.{name}"))
+                    .or_insert_with(|| json_string_literal(value));
+            }
+        }
+    }
+
+    if dev {
+        defines
+            .entry("process.env.NODE_ENV".to_string())
+            .or_insert_with(|| json_string_literal("development"));
+    }
+
+    Ok(defines)
+}
+
+// Bundle a single entry with incremental builds enabled, reporting its watch
+// set and emitted outputs.
+#[cfg(not(test))]
+async fn build_entry(
+    input: &NormalizedInput,
+    cwd: &Path,
+    output_dir_string: &str,
+    minify: bool,
+    defines: &BTreeMap<String, String>,
+) -> Result<EntryBuild, PyErr> {
+    let options = BundlerOptions {
+        input: Some(vec![InputItem {
+            name: Some(input.name.to_string()),
+            import: input.import.to_string(),
+        }]),
+        cwd: Some(cwd.to_path_buf()),
+        dir: Some(output_dir_string.to_owned()),
+        entry_filenames: Some("[name].js".to_string().into()),
+        css_entry_filenames: Some("[name].css".to_string().into()),
+        minify: Some(minify.into()),
+        define: Some(defines.clone().into_iter().collect()),
+        experimental: Some(ExperimentalOptions {
+            incremental_build: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut bundler =
+        Bundler::new(options).map_err(|err| py_runtime_error("failed to initialize Bundler", err))?;
+    let output = bundler
+        .write()
+        .await
+        .map_err(|err| py_runtime_error("bundling failed", err))?;
+
+    let watch_files = output
+        .watch_files
+        .iter()
+        .filter_map(|file| Path::new(file.as_str()).canonicalize().ok())
+        .map(|path| dunce::simplified(&path).to_path_buf())
+        .collect();
+    let outputs = output
+        .assets
+        .iter()
+        .map(|asset| asset.filename().to_string())
+        .collect();
+    Ok(EntryBuild {
+        watch_files,
+        outputs,
+    })
+}
+
+// Invoke the optional dev-mode callback with a freshly-built event payload. The
+// callback receives a dict carrying a `type` discriminator plus event-specific
+// fields, mirroring a file-watcher's rebuild lifecycle.
+#[cfg(not(test))]
+fn emit_dev_event(
+    callback: &Py<PyAny>,
+    build: impl for<'py> FnOnce(Python<'py>) -> PyResult<Bound<'py, PyDict>>,
+) -> PyResult<()> {
+    Python::with_gil(|py| {
+        let payload = build(py)?;
+        callback.call1(py, (payload,)).map(|_| ())
+    })
+}
+
 #[cfg(not(test))]
 fn map_bundle_error(err: BundleError) -> PyErr {
     match err {
@@ -216,14 +575,20 @@ fn map_bundle_error(err: BundleError) -> PyErr {
 mod _core {
     use super::*;
 
-    #[pyfunction(signature = (paths, dev = false, minify = true, output = None, cwd = None))]
+    #[pyfunction(signature = (paths, dev = false, minify = true, hashed = false, output = None, cwd = None, ignore = Vec::new(), define = HashMap::new(), env_prefix = None, on_event = None))]
+    #[allow(clippy::too_many_arguments)]
     fn bundle(
         py: Python<'_>,
         paths: HashSet<PathBuf>,
         dev: bool,
         minify: bool,
+        hashed: bool,
         output: Option<PathBuf>,
         cwd: Option<PathBuf>,
+        ignore: Vec<String>,
+        define: HashMap<String, String>,
+        env_prefix: Option<String>,
+        on_event: Option<Py<PyAny>>,
     ) -> PyResult<Bound<'_, PyAny>> {
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
             let cwd = match cwd {
@@ -240,66 +605,213 @@ mod _core {
             let output_dir_string =
                 path_to_utf8(&output_dir, "output path").map_err(map_bundle_error)?;
 
+            let paths = expand_input_paths(paths, &cwd, &ignore).map_err(map_bundle_error)?;
             let normalized =
                 normalize_inputs(paths, &cwd, &output_dir).map_err(map_bundle_error)?;
-            let input_items = normalized
-                .into_iter()
-                .map(|item| InputItem {
-                    name: Some(item.name),
-                    import: item.import,
-                })
-                .collect::<Vec<_>>();
-
-            let mut options = BundlerOptions {
-                input: Some(input_items),
-                cwd: Some(cwd),
-                dir: Some(output_dir_string),
-                entry_filenames: Some("[name].js".to_string().into()),
-                css_entry_filenames: Some("[name].css".to_string().into()),
-                minify: Some(minify.into()),
-                ..Default::default()
-            };
 
-            if dev {
-                options.experimental = Some(ExperimentalOptions {
-                    incremental_build: Some(true),
-                    ..Default::default()
-                });
-            }
+            // Snapshot env vars at build time so the substitutions are
+            // deterministic rather than read by the bundled runtime.
+            let env_vars: Vec<(String, String)> = std::env::vars().collect();
+            let defines = build_defines(define, env_prefix.as_deref(), &env_vars, dev)
+                .map_err(map_bundle_error)?;
 
             if dev {
-                let bundler_config = BundlerConfig::new(options, vec![]);
-                let dev_engine = Arc::new(
-                    DevEngine::new(
-                        bundler_config,
-                        DevOptions {
-                            rebuild_strategy: Some(RebuildStrategy::Always),
-                            ..Default::default()
-                        },
-                    )
-                    .map_err(|err| py_runtime_error("failed to initialize DevEngine", err))?,
-                );
-
-                let mut close_guard = DevEngineCloseGuard::new(Arc::clone(&dev_engine));
-
-                dev_engine
-                    .run()
-                    .await
-                    .map_err(|err| py_runtime_error("failed to start DevEngine", err))?;
-                dev_engine
-                    .wait_for_close()
-                    .await
-                    .map_err(|err| py_runtime_error("DevEngine exited with an error", err))?;
+                // Build each entry once to learn the module set that feeds its
+                // output, then watch those sets and rebuild only the entries a
+                // change actually reaches instead of re-bundling everything.
+                let mut graph = DependencyGraph::default();
+                for input in &normalized {
+                    let build =
+                        build_entry(input, &cwd, &output_dir_string, minify, &defines).await?;
+                    graph.record(input.import.clone(), build.watch_files);
+                }
+
+                // The watcher covers the whole project recursively, but every
+                // rebuild writes into the output directory *inside* it. Those
+                // writes are unknown paths, so without excluding them each
+                // rebuild would re-trigger a full rebuild forever. Resolve the
+                // output root once so change events under it can be dropped.
+                let output_root = {
+                    let joined = if output_dir.is_absolute() {
+                        output_dir.clone()
+                    } else {
+                        cwd.join(&output_dir)
+                    };
+                    joined
+                        .canonicalize()
+                        .map(|resolved| dunce::simplified(&resolved).to_path_buf())
+                        .unwrap_or(joined)
+                };
+
+                let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+                let mut watcher = notify::recommended_watcher(move |event| {
+                    let _ = tx.send(event);
+                })
+                .map_err(|err| py_runtime_error("failed to initialize file watcher", err))?;
+                watcher
+                    .watch(&cwd, RecursiveMode::Recursive)
+                    .map_err(|err| py_runtime_error("failed to watch project directory", err))?;
+
+                while let Some(event) = rx.recv().await {
+                    let Ok(event) = event else { continue };
+                    let changed: HashSet<PathBuf> = event
+                        .paths
+                        .into_iter()
+                        .map(|path| {
+                            // A deleted or renamed-away file no longer
+                            // canonicalizes; fall back to the raw event path so
+                            // removals still trigger a graph re-resolution
+                            // instead of being silently dropped.
+                            match path.canonicalize() {
+                                Ok(resolved) => dunce::simplified(&resolved).to_path_buf(),
+                                Err(_) => dunce::simplified(&path).to_path_buf(),
+                            }
+                        })
+                        // Drop events for the build's own outputs so a rebuild
+                        // never sees its writes as a fresh change.
+                        .filter(|path| !path.starts_with(&output_root))
+                        .collect();
+                    if changed.is_empty() {
+                        continue;
+                    }
+
+                    if let Some(callback) = &on_event {
+                        let changed_paths: Vec<String> = changed
+                            .iter()
+                            .map(|path| path.display().to_string())
+                            .collect();
+                        emit_dev_event(callback, |py| {
+                            let payload = PyDict::new(py);
+                            payload.set_item("type", "files_changed")?;
+                            payload.set_item("paths", changed_paths)?;
+                            Ok(payload)
+                        })?;
+                    }
+
+                    let targets = match plan_rebuild(&graph, &changed) {
+                        RebuildPlan::Full => {
+                            normalized.iter().map(|input| input.import.clone()).collect()
+                        }
+                        RebuildPlan::Partial(entries) => entries,
+                    };
+
+                    if let Some(callback) = &on_event {
+                        emit_dev_event(callback, |py| {
+                            let payload = PyDict::new(py);
+                            payload.set_item("type", "build_start")?;
+                            Ok(payload)
+                        })?;
+                    }
+
+                    let started = std::time::Instant::now();
+                    let mut outputs = Vec::new();
+                    let mut build_error = None;
+                    for import in targets {
+                        let Some(input) = normalized.iter().find(|input| input.import == import)
+                        else {
+                            continue;
+                        };
+                        match build_entry(input, &cwd, &output_dir_string, minify, &defines).await {
+                            Ok(build) => {
+                                outputs.extend(build.outputs);
+                                graph.record(import, build.watch_files);
+                            }
+                            Err(err) => {
+                                build_error = Some(err);
+                                break;
+                            }
+                        }
+                    }
+
+                    if let Some(callback) = &on_event {
+                        match build_error {
+                            Some(err) => {
+                                let message =
+                                    Python::with_gil(|py| err.value(py).to_string());
+                                emit_dev_event(callback, |py| {
+                                    let payload = PyDict::new(py);
+                                    payload.set_item("type", "build_error")?;
+                                    payload.set_item("error", message)?;
+                                    Ok(payload)
+                                })?;
+                            }
+                            None => {
+                                let elapsed_ms = started.elapsed().as_millis();
+                                emit_dev_event(callback, |py| {
+                                    let payload = PyDict::new(py);
+                                    payload.set_item("type", "build_success")?;
+                                    payload.set_item("elapsed_ms", elapsed_ms)?;
+                                    payload.set_item("outputs", outputs)?;
+                                    Ok(payload)
+                                })?;
+                            }
+                        }
+                    }
+                }
 
-                close_guard.disarm();
                 Ok(())
             } else {
+                let input_items = normalized
+                    .iter()
+                    .map(|item| InputItem {
+                        name: Some(item.name.to_string()),
+                        import: item.import.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+
+                let manifest_dir = if output_dir.is_absolute() {
+                    output_dir.clone()
+                } else {
+                    cwd.join(&output_dir)
+                };
+
+                let (entry_template, css_template) = if hashed {
+                    ("[name].[hash].js", "[name].[hash].css")
+                } else {
+                    ("[name].js", "[name].css")
+                };
+
+                let options = BundlerOptions {
+                    input: Some(input_items),
+                    cwd: Some(cwd),
+                    dir: Some(output_dir_string),
+                    entry_filenames: Some(entry_template.to_string().into()),
+                    css_entry_filenames: Some(css_template.to_string().into()),
+                    minify: Some(minify.into()),
+                    define: Some(defines.into_iter().collect()),
+                    ..Default::default()
+                };
+
                 let mut bundler = Bundler::new(options)
                     .map_err(|err| py_runtime_error("failed to initialize Bundler", err))?;
-                bundler
+                let output = bundler
                     .write()
                     .await
                     .map_err(|err| py_runtime_error("bundling failed", err))?;
+
+                // Map each entry chunk and CSS asset back to the input that
+                // produced it, then persist the lookup as `manifest.json`.
+                let mut entry_js = HashMap::new();
+                let mut css_files = Vec::new();
+                for asset in &output.assets {
+                    match asset {
+                        Output::Chunk(chunk) if chunk.is_entry => {
+                            if let Some(name) = &chunk.name {
+                                entry_js.insert(name.to_string(), chunk.filename.to_string());
+                            }
+                        }
+                        Output::Asset(asset) if asset.filename.ends_with(".css") => {
+                            css_files.push(asset.filename.to_string());
+                        }
+                        _ => {}
+                    }
+                }
+
+                let manifest = build_manifest(&normalized, &entry_js, &css_files);
+                let manifest_json = deno_core::serde_json::to_string_pretty(&manifest)
+                    .map_err(|err| py_runtime_error("failed to serialize build manifest", err))?;
+                std::fs::write(manifest_dir.join("manifest.json"), manifest_json)
+                    .map_err(|err| py_runtime_error("failed to write build manifest", err))?;
                 Ok(())
             }
         })
@@ -388,6 +900,100 @@ mod tests {
         assert!(err.to_string().contains("same output"));
     }
 
+    #[test]
+    fn builds_manifest_keyed_by_import_with_hashed_outputs() {
+        let inputs = vec![NormalizedInput {
+            import: RcStr::from("home/page.tsx"),
+            name: RcStr::from("home/page"),
+            output_relative_js: PathBuf::from("home/page.js"),
+        }];
+
+        let entry_js = HashMap::from([(
+            "home/page".to_string(),
+            "home/page.a1b2c3.js".to_string(),
+        )]);
+        let css_files = vec!["home/page.d4e5f6.css".to_string()];
+
+        let manifest = build_manifest(&inputs, &entry_js, &css_files);
+        let entry = manifest
+            .get("home/page.tsx")
+            .expect("expected manifest entry for home/page.tsx");
+        assert_eq!(entry.name, "home/page");
+        assert_eq!(entry.js, "home/page.a1b2c3.js");
+        assert_eq!(entry.css.as_deref(), Some("home/page.d4e5f6.css"));
+    }
+
+    #[test]
+    fn entry_css_matches_only_its_own_outputs() {
+        assert!(is_entry_css("home/page.css", "home/page"));
+        assert!(is_entry_css("home/page.a1b2c3.css", "home/page"));
+        assert!(!is_entry_css("home/page2.css", "home/page"));
+        assert!(!is_entry_css("home/page.js", "home/page"));
+    }
+
+    #[test]
+    fn rebuilds_only_entries_whose_dependencies_changed() {
+        let mut graph = DependencyGraph::default();
+        graph.record(
+            RcStr::from("home/page.tsx"),
+            HashSet::from([PathBuf::from("/app/home/page.tsx"), PathBuf::from("/app/shared.ts")]),
+        );
+        graph.record(
+            RcStr::from("about/page.tsx"),
+            HashSet::from([PathBuf::from("/app/about/page.tsx")]),
+        );
+
+        let changed = HashSet::from([PathBuf::from("/app/shared.ts")]);
+        let plan = plan_rebuild(&graph, &changed);
+        assert_eq!(
+            plan,
+            RebuildPlan::Partial(HashSet::from([RcStr::from("home/page.tsx")]))
+        );
+    }
+
+    #[test]
+    fn unknown_changed_file_forces_full_rebuild() {
+        let mut graph = DependencyGraph::default();
+        graph.record(
+            RcStr::from("home/page.tsx"),
+            HashSet::from([PathBuf::from("/app/home/page.tsx")]),
+        );
+
+        let changed = HashSet::from([PathBuf::from("/app/newly-added.ts")]);
+        assert_eq!(plan_rebuild(&graph, &changed), RebuildPlan::Full);
+    }
+
+    #[test]
+    fn expands_directory_roots_to_supported_files() {
+        let project = TempProject::new();
+        project.create_file("pages/home.tsx");
+        project.create_file("pages/about.jsx");
+        project.create_file("pages/readme.md");
+
+        let paths = HashSet::from([PathBuf::from("pages")]);
+        let expanded =
+            expand_input_paths(paths, &project.root, &[]).expect("expected directory expansion");
+
+        assert_eq!(expanded.len(), 2);
+        assert!(expanded.contains(&project.root.join("pages/home.tsx")));
+        assert!(expanded.contains(&project.root.join("pages/about.jsx")));
+    }
+
+    #[test]
+    fn expands_globs_and_honors_ignore_list() {
+        let project = TempProject::new();
+        project.create_file("src/page.tsx");
+        project.create_file("src/page.test.tsx");
+
+        let paths = HashSet::from([PathBuf::from("src/**/*.tsx")]);
+        let ignore = vec!["**/*.test.tsx".to_string()];
+        let expanded = expand_input_paths(paths, &project.root, &ignore)
+            .expect("expected glob expansion");
+
+        assert_eq!(expanded.len(), 1);
+        assert!(expanded.contains(&project.root.join("src/page.tsx")));
+    }
+
     #[test]
     fn preserves_relative_structure_for_output_mapping() {
         let project = TempProject::new();
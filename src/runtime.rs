@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
 use deno_core::{
-    JsRuntime, OpState, PollEventLoopOptions, RuntimeOptions, op2, serde_json::Value, v8,
+    JsRuntime, ModuleLoadResponse, ModuleLoader, ModuleSource, ModuleSourceCode, ModuleSpecifier,
+    ModuleType, OpState, PollEventLoopOptions, RequestedModuleType, ResolutionKind, RuntimeOptions,
+    error::ModuleLoaderError, op2, serde_json::Value, v8,
 };
 
 #[cfg(not(test))]
@@ -8,17 +13,44 @@ use pyo3::{
     prelude::*,
     types::{PyDict, PyList},
 };
+#[cfg(not(test))]
+use std::sync::atomic::{AtomicU64, Ordering};
 
 #[cfg_attr(test, allow(dead_code))]
 #[derive(Debug)]
 enum RuntimeError {
-    Execution(String),
+    Execution(JsExecutionError),
     Deserialize(String),
 }
 
+// Structured form of a JavaScript failure, extracted from deno_core's `JsError`
+// so Python can surface a real traceback instead of an opaque debug string.
+#[cfg_attr(test, allow(dead_code))]
+#[derive(Debug, Default)]
+struct JsExecutionError {
+    message: String,
+    source_line: Option<String>,
+    script_resource_name: Option<String>,
+    line_number: Option<i64>,
+    start_column: Option<i64>,
+    frames: Vec<JsStackFrameInfo>,
+}
+
+#[cfg_attr(test, allow(dead_code))]
+#[derive(Debug)]
+struct JsStackFrameInfo {
+    function_name: Option<String>,
+    script_name: Option<String>,
+    line_number: Option<i64>,
+    column_number: Option<i64>,
+}
+
 impl RuntimeError {
     fn execution(message: impl Into<String>) -> Self {
-        Self::Execution(message.into())
+        Self::Execution(JsExecutionError {
+            message: message.into(),
+            ..Default::default()
+        })
     }
 
     fn deserialize(message: impl Into<String>) -> Self {
@@ -26,22 +58,197 @@ impl RuntimeError {
     }
 }
 
+// Translate a structured `JsError` into our own representation, lifting the
+// exception location from the topmost stack frame.
+fn js_execution_from(js: &deno_core::error::JsError) -> JsExecutionError {
+    let frames = js
+        .frames
+        .iter()
+        .map(|frame| JsStackFrameInfo {
+            function_name: frame.function_name.clone(),
+            script_name: frame.file_name.clone(),
+            line_number: frame.line_number,
+            column_number: frame.column_number,
+        })
+        .collect::<Vec<_>>();
+    let top = js.frames.first();
+
+    JsExecutionError {
+        message: js.exception_message.clone(),
+        source_line: js.source_line.clone(),
+        script_resource_name: top.and_then(|frame| frame.file_name.clone()),
+        line_number: top.and_then(|frame| frame.line_number),
+        start_column: top.and_then(|frame| frame.column_number),
+        frames,
+    }
+}
+
+// Map an error raised while driving a module or the event loop. JavaScript
+// exceptions keep their structured detail; anything else degrades to a plain
+// message.
+fn js_execution_error(err: deno_core::error::CoreError) -> RuntimeError {
+    if let deno_core::error::CoreError::Js(js_error) = &err {
+        return RuntimeError::Execution(js_execution_from(js_error));
+    }
+    RuntimeError::execution(format!("Execution error: {err:?}"))
+}
+
 #[derive(Default)]
 struct SsrCapture {
     html: Option<String>,
 }
 
+// Serves caller-registered ES modules by specifier. Relative imports resolve
+// against the synthetic entry URL; the `gdansk:runtime` import keeps resolving
+// from the snapshot (the loader is never asked to `load` it). Any specifier the
+// caller did not register is a load error rather than a silent empty module.
+#[derive(Default)]
+struct GdanskModuleLoader {
+    modules: HashMap<String, String>,
+}
+
+impl ModuleLoader for GdanskModuleLoader {
+    fn resolve(
+        &self,
+        specifier: &str,
+        referrer: &str,
+        _kind: ResolutionKind,
+    ) -> Result<ModuleSpecifier, ModuleLoaderError> {
+        Ok(deno_core::resolve_import(specifier, referrer)?)
+    }
+
+    fn load(
+        &self,
+        module_specifier: &ModuleSpecifier,
+        _maybe_referrer: Option<&ModuleSpecifier>,
+        _is_dyn_import: bool,
+        _requested_module_type: RequestedModuleType,
+    ) -> ModuleLoadResponse {
+        match self.modules.get(module_specifier.as_str()) {
+            Some(source) => {
+                let module = ModuleSource::new(
+                    ModuleType::JavaScript,
+                    ModuleSourceCode::String(source.clone().into()),
+                    module_specifier,
+                    None,
+                );
+                ModuleLoadResponse::Sync(Ok(module))
+            }
+            None => ModuleLoadResponse::Sync(Err(ModuleLoaderError::generic(format!(
+                "Cannot load module: unknown specifier {module_specifier}"
+            )))),
+        }
+    }
+}
+
+// Buffers the text a script writes through the overridden `console.*`, split
+// into stdout (`log`/`info`/`debug`) and stderr (`warn`/`error`) streams.
+#[derive(Default)]
+struct StdioCapture {
+    stdout: String,
+    stderr: String,
+}
+
+// One test case's outcome as reported by the bundled harness through the
+// `reportTestResult` runtime op. `status` is the raw string the harness emits
+// (`"ok"` or `"failed"`); the caller maps it onto its own event enum, where
+// `"ignored"` is also recognised.
+#[cfg_attr(test, allow(dead_code))]
+#[derive(Debug, Clone)]
+pub(crate) struct ReportedTest {
+    pub(crate) name: String,
+    pub(crate) status: String,
+    pub(crate) duration_ms: u128,
+    pub(crate) message: Option<String>,
+}
+
+// Collects the cases a single test module reports as its harness runs them.
+#[derive(Default)]
+struct TestCapture {
+    results: Vec<ReportedTest>,
+}
+
 #[op2(fast)]
 fn op_gdansk_set_html(state: &mut OpState, #[string] html: String) {
     state.borrow_mut::<SsrCapture>().html = Some(html);
 }
 
+#[op2(fast)]
+fn op_gdansk_write_stdout(state: &mut OpState, #[string] text: String) {
+    state.borrow_mut::<StdioCapture>().stdout.push_str(&text);
+}
+
+#[op2(fast)]
+fn op_gdansk_write_stderr(state: &mut OpState, #[string] text: String) {
+    state.borrow_mut::<StdioCapture>().stderr.push_str(&text);
+}
+
+#[op2(fast)]
+fn op_gdansk_report_test_result(
+    state: &mut OpState,
+    #[string] name: String,
+    #[string] status: String,
+    duration_ms: f64,
+    #[string] message: Option<String>,
+) {
+    state.borrow_mut::<TestCapture>().results.push(ReportedTest {
+        name,
+        status,
+        duration_ms: duration_ms.max(0.0) as u128,
+        message,
+    });
+}
+
 deno_core::extension!(
     gdansk_runtime_ext,
-    ops = [op_gdansk_set_html],
-    state = |state| state.put(SsrCapture::default())
+    ops = [
+        op_gdansk_set_html,
+        op_gdansk_write_stdout,
+        op_gdansk_write_stderr,
+        op_gdansk_report_test_result
+    ],
+    state = |state| {
+        state.put(SsrCapture::default());
+        state.put(StdioCapture::default());
+        state.put(TestCapture::default());
+    }
 );
 
+// JS prelude that routes `console.*` through the capture ops. Prepended to the
+// evaluated module so logging is collected rather than discarded.
+const CONSOLE_OVERRIDE: &str = r#"globalThis.console = {
+  log: (...args) => Deno.core.ops.op_gdansk_write_stdout(args.map(String).join(" ") + "\n"),
+  info: (...args) => Deno.core.ops.op_gdansk_write_stdout(args.map(String).join(" ") + "\n"),
+  debug: (...args) => Deno.core.ops.op_gdansk_write_stdout(args.map(String).join(" ") + "\n"),
+  warn: (...args) => Deno.core.ops.op_gdansk_write_stderr(args.map(String).join(" ") + "\n"),
+  error: (...args) => Deno.core.ops.op_gdansk_write_stderr(args.map(String).join(" ") + "\n"),
+};
+"#;
+
+// Reset script run before each pooled evaluation: drop the previous result and
+// any globals the last snippet introduced, so a warm runtime behaves like a
+// freshly constructed one. `__gdansk_base_globals` records the property names
+// present right after warm-up (including `console`, `__gdansk_runCode`, and
+// itself), so only caller-introduced globals are deleted.
+#[cfg(not(test))]
+const POOL_RESET: &str = r#"delete globalThis.__gdansk_last_result;
+for (const key of Object.getOwnPropertyNames(globalThis)) {
+  if (!globalThis.__gdansk_base_globals.has(key)) {
+    try { delete globalThis[key]; } catch (_) {}
+  }
+}
+"#;
+
+// The result of an evaluation: the deserialized value plus whatever the script
+// logged, each reset per call the same way `SsrCapture.html` is taken.
+#[cfg_attr(test, allow(dead_code))]
+#[derive(Debug)]
+struct EvalOutput {
+    value: Value,
+    stdout: String,
+    stderr: String,
+}
+
 fn execution_error(err: impl std::fmt::Debug) -> RuntimeError {
     RuntimeError::execution(format!("Execution error: {err:?}"))
 }
@@ -82,9 +289,55 @@ fn read_json_value(
         .map_err(|err| RuntimeError::deserialize(format!("Cannot deserialize value: {err:?}")))
 }
 
-async fn evaluate(code: &str) -> Result<Value, RuntimeError> {
+// True when the global handle currently refers to a pending/settled promise.
+fn is_promise(runtime: &mut JsRuntime, value: &v8::Global<v8::Value>) -> bool {
+    deno_core::scope!(scope, runtime);
+    let local = v8::Local::new(scope, value);
+    local.is_promise()
+}
+
+// Drive the event loop until `promise` settles, returning the fulfilled value or
+// turning a rejection into an execution error carrying its reason.
+async fn resolve_promise(
+    runtime: &mut JsRuntime,
+    promise: v8::Global<v8::Value>,
+) -> Result<v8::Global<v8::Value>, RuntimeError> {
+    let resolve = runtime.resolve(promise);
+    runtime
+        .with_event_loop_promise(resolve, PollEventLoopOptions::default())
+        .await
+        .map_err(js_execution_error)
+}
+
+// Render an optional props object into a frozen `globalThis.props` assignment,
+// prepended to the evaluated module so a script can read server-provided data.
+// An empty string when no props are supplied keeps the module unchanged.
+fn props_prelude(props: Option<&Value>) -> Result<String, RuntimeError> {
+    match props {
+        Some(props) => {
+            let props_json = deno_core::serde_json::to_string(props).map_err(execution_error)?;
+            let props_literal =
+                deno_core::serde_json::to_string(&props_json).map_err(execution_error)?;
+            Ok(format!(
+                "globalThis.props = Object.freeze(JSON.parse({props_literal}));\n"
+            ))
+        }
+        None => Ok(String::new()),
+    }
+}
+
+async fn evaluate(
+    code: &str,
+    await_promises: bool,
+    props: Option<Value>,
+    modules: Option<HashMap<String, String>>,
+) -> Result<EvalOutput, RuntimeError> {
+    let loader = Rc::new(GdanskModuleLoader {
+        modules: modules.unwrap_or_default(),
+    });
     let mut runtime = JsRuntime::new(RuntimeOptions {
         startup_snapshot: Some(snapshot()),
+        module_loader: Some(loader),
         extensions: vec![gdansk_runtime_ext::init()],
         ..Default::default()
     });
@@ -93,26 +346,171 @@ async fn evaluate(code: &str) -> Result<Value, RuntimeError> {
         let op_state = runtime.op_state();
         let mut op_state = op_state.borrow_mut();
         op_state.borrow_mut::<SsrCapture>().html = None;
+        *op_state.borrow_mut::<StdioCapture>() = StdioCapture::default();
     }
 
     let module_specifier =
         deno_core::resolve_url("file:///gdansk/runtime_eval.js").map_err(execution_error)?;
     let code_json = deno_core::serde_json::to_string(code).map_err(execution_error)?;
+    let props_prelude = props_prelude(props.as_ref())?;
     let module_code = format!(
-        "import {{ runCode }} from \"gdansk:runtime\";\nglobalThis.__gdansk_last_result = runCode({code_json});"
+        "{CONSOLE_OVERRIDE}\n{props_prelude}import {{ runCode }} from \"gdansk:runtime\";\nglobalThis.__gdansk_last_result = runCode({code_json});"
     );
 
     let mod_id = runtime
         .load_main_es_module_from_code(&module_specifier, module_code)
         .await
-        .map_err(execution_error)?;
+        .map_err(js_execution_error)?;
+
+    let result = runtime.mod_evaluate(mod_id);
+    runtime
+        .run_event_loop(PollEventLoopOptions::default())
+        .await
+        .map_err(js_execution_error)?;
+    result.await.map_err(js_execution_error)?;
+
+    let html = {
+        let op_state = runtime.op_state();
+        let mut op_state = op_state.borrow_mut();
+        op_state.borrow_mut::<SsrCapture>().html.take()
+    };
+
+    let value = if let Some(html) = html {
+        Value::String(html)
+    } else {
+        let output = runtime
+            .execute_script("<gdansk-runtime-result>", "globalThis.__gdansk_last_result")
+            .map_err(js_execution_error)?;
+
+        let output = if await_promises && is_promise(&mut runtime, &output) {
+            resolve_promise(&mut runtime, output).await?
+        } else {
+            output
+        };
+
+        read_json_value(&mut runtime, output)?
+    };
+
+    let StdioCapture { stdout, stderr } = {
+        let op_state = runtime.op_state();
+        let mut op_state = op_state.borrow_mut();
+        std::mem::take(op_state.borrow_mut::<StdioCapture>())
+    };
+
+    Ok(EvalOutput {
+        value,
+        stdout,
+        stderr,
+    })
+}
+
+// Execute a bundled test module and collect the outcomes its harness reports
+// through `op_gdansk_report_test_result`. The bundle is self-contained (the
+// `gdansk:runtime` helpers are inlined at build time), so it is run as a plain
+// script and the event loop is driven to completion to flush the harness's
+// top-level `await`. A failure before any case reports — a syntax error or a
+// throw in module scope — surfaces as the error message so the caller can mark
+// the whole file failed.
+#[cfg(not(test))]
+async fn evaluate_test_module(name: &str, source: &str) -> Result<Vec<ReportedTest>, RuntimeError> {
+    let mut runtime = JsRuntime::new(RuntimeOptions {
+        startup_snapshot: Some(snapshot()),
+        extensions: vec![gdansk_runtime_ext::init()],
+        ..Default::default()
+    });
+
+    {
+        let op_state = runtime.op_state();
+        let mut op_state = op_state.borrow_mut();
+        op_state.borrow_mut::<TestCapture>().results.clear();
+    }
+
+    let resource_name = format!("<gdansk-test:{name}>");
+    runtime
+        .execute_script(resource_name, source.to_owned())
+        .map_err(js_execution_error)?;
+    runtime
+        .run_event_loop(PollEventLoopOptions::default())
+        .await
+        .map_err(js_execution_error)?;
+
+    let results = {
+        let op_state = runtime.op_state();
+        let mut op_state = op_state.borrow_mut();
+        std::mem::take(&mut op_state.borrow_mut::<TestCapture>().results)
+    };
+    Ok(results)
+}
 
+// Run one bundled test module to completion on a dedicated current-thread
+// runtime (the `JsRuntime` is `!Send`), returning the reported cases or the
+// execution error's message. Mirrors `run`'s off-thread evaluation so the
+// caller can await it from the async bundler context.
+#[cfg(not(test))]
+pub(crate) fn run_test_module(name: &str, source: String) -> Result<Vec<ReportedTest>, String> {
+    let name = name.to_owned();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| format!("{err}"))?;
+    runtime
+        .block_on(evaluate_test_module(&name, &source))
+        .map_err(|err| match err {
+            RuntimeError::Execution(info) => info.message,
+            RuntimeError::Deserialize(message) => message,
+        })
+}
+
+// Load the runtime's `runCode` entrypoint once and stash it on `globalThis`, so
+// a long-lived session can invoke it for every subsequent snippet without
+// re-importing the main module (which a runtime accepts only once).
+#[cfg(not(test))]
+async fn prepare_session_runtime(runtime: &mut JsRuntime) -> Result<(), RuntimeError> {
+    let module_specifier =
+        deno_core::resolve_url("file:///gdansk/session.js").map_err(execution_error)?;
+    let module_code =
+        "import { runCode } from \"gdansk:runtime\";\nglobalThis.__gdansk_runCode = runCode;"
+            .to_string();
+
+    let mod_id = runtime
+        .load_main_es_module_from_code(&module_specifier, module_code)
+        .await
+        .map_err(js_execution_error)?;
     let result = runtime.mod_evaluate(mod_id);
     runtime
         .run_event_loop(PollEventLoopOptions::default())
         .await
-        .map_err(execution_error)?;
-    result.await.map_err(execution_error)?;
+        .map_err(js_execution_error)?;
+    result.await.map_err(js_execution_error)?;
+    Ok(())
+}
+
+// Evaluate one snippet against an already-prepared session runtime. `SsrCapture`
+// is cleared per call, but `globalThis` is left intact so state set by earlier
+// evaluations remains visible.
+#[cfg(not(test))]
+async fn session_eval(
+    runtime: &mut JsRuntime,
+    code: &str,
+    execution_count: u64,
+) -> Result<Value, RuntimeError> {
+    {
+        let op_state = runtime.op_state();
+        let mut op_state = op_state.borrow_mut();
+        op_state.borrow_mut::<SsrCapture>().html = None;
+    }
+
+    let code_json = deno_core::serde_json::to_string(code).map_err(execution_error)?;
+    let script =
+        format!("globalThis.__gdansk_last_result = globalThis.__gdansk_runCode({code_json});");
+    let resource_name = format!("<gdansk-session-eval:{execution_count}>");
+    runtime
+        .execute_script(resource_name, script)
+        .map_err(js_execution_error)?;
+    runtime
+        .run_event_loop(PollEventLoopOptions::default())
+        .await
+        .map_err(js_execution_error)?;
 
     let html = {
         let op_state = runtime.op_state();
@@ -125,16 +523,389 @@ async fn evaluate(code: &str) -> Result<Value, RuntimeError> {
     }
 
     let output = runtime
-        .execute_script("<gdansk-runtime-result>", "globalThis.__gdansk_last_result")
-        .map_err(execution_error)?;
+        .execute_script("<gdansk-session-result>", "globalThis.__gdansk_last_result")
+        .map_err(js_execution_error)?;
+
+    read_json_value(runtime, output)
+}
+
+// A single evaluation handed to the session worker thread, paired with the
+// channel the worker answers on.
+#[cfg(not(test))]
+struct SessionRequest {
+    code: String,
+    execution_count: u64,
+    responder: tokio::sync::oneshot::Sender<Result<Value, RuntimeError>>,
+}
+
+// Spawn the dedicated thread that owns the session's `!Send` `JsRuntime`,
+// returning a sender once the runtime has been initialized. Eval requests are
+// serviced one at a time in arrival order so state mutations stay ordered.
+#[cfg(not(test))]
+fn spawn_session_worker() -> Result<std::sync::mpsc::Sender<SessionRequest>, RuntimeError> {
+    let (request_tx, request_rx) = std::sync::mpsc::channel::<SessionRequest>();
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel::<Result<(), RuntimeError>>();
+
+    std::thread::spawn(move || {
+        let tokio_runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                let _ = ready_tx.send(Err(execution_error(err)));
+                return;
+            }
+        };
+
+        tokio_runtime.block_on(async move {
+            let mut runtime = JsRuntime::new(RuntimeOptions {
+                startup_snapshot: Some(snapshot()),
+                extensions: vec![gdansk_runtime_ext::init()],
+                ..Default::default()
+            });
+
+            if let Err(err) = prepare_session_runtime(&mut runtime).await {
+                let _ = ready_tx.send(Err(err));
+                return;
+            }
+            let _ = ready_tx.send(Ok(()));
 
-    read_json_value(&mut runtime, output)
+            while let Ok(request) = request_rx.recv() {
+                let result = session_eval(&mut runtime, &request.code, request.execution_count).await;
+                let _ = request.responder.send(result);
+            }
+        });
+    });
+
+    ready_rx.recv().map_err(execution_error)??;
+    Ok(request_tx)
+}
+
+// A persistent evaluation session: a single `JsRuntime` pinned to its own thread
+// that preserves `globalThis` across `eval` calls. The execution counter lets
+// callers correlate each result with the snippet that produced it.
+#[cfg(not(test))]
+#[pyclass]
+pub(crate) struct Session {
+    sender: std::sync::mpsc::Sender<SessionRequest>,
+    execution_count: AtomicU64,
+}
+
+#[cfg(not(test))]
+#[pymethods]
+impl Session {
+    #[new]
+    fn new() -> PyResult<Self> {
+        let sender = spawn_session_worker().map_err(map_runtime_error)?;
+        Ok(Self {
+            sender,
+            execution_count: AtomicU64::new(0),
+        })
+    }
+
+    fn eval<'py>(&self, py: Python<'py>, code: &str) -> PyResult<Bound<'py, PyAny>> {
+        let code = code.to_owned();
+        let execution_count = self.execution_count.fetch_add(1, Ordering::SeqCst) + 1;
+        let sender = self.sender.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let (responder, response) = tokio::sync::oneshot::channel();
+            sender
+                .send(SessionRequest {
+                    code,
+                    execution_count,
+                    responder,
+                })
+                .map_err(|err| map_runtime_error(execution_error(err)))?;
+            let value = response
+                .await
+                .map_err(|err| map_runtime_error(execution_error(err)))?
+                .map_err(map_runtime_error)?;
+            Python::attach(|py| json_to_py(py, &value))
+        })
+    }
+
+    #[getter]
+    fn execution_count(&self) -> u64 {
+        self.execution_count.load(Ordering::SeqCst)
+    }
+}
+
+// Warm a pooled runtime: install the `console.*` override, stash `runCode` for
+// reuse, and snapshot the baseline global names so `POOL_RESET` can tell which
+// globals a later snippet introduced. Mirrors `prepare_session_runtime`, which
+// deliberately preserves globals; the pool instead discards them per call.
+#[cfg(not(test))]
+async fn prepare_pool_runtime(runtime: &mut JsRuntime) -> Result<(), RuntimeError> {
+    let module_specifier =
+        deno_core::resolve_url("file:///gdansk/pool.js").map_err(execution_error)?;
+    let module_code = format!(
+        "{CONSOLE_OVERRIDE}\nimport {{ runCode }} from \"gdansk:runtime\";\nglobalThis.__gdansk_runCode = runCode;\nglobalThis.__gdansk_base_globals = null;\nglobalThis.__gdansk_base_globals = new Set(Object.getOwnPropertyNames(globalThis));"
+    );
+
+    let mod_id = runtime
+        .load_main_es_module_from_code(&module_specifier, module_code)
+        .await
+        .map_err(js_execution_error)?;
+    let result = runtime.mod_evaluate(mod_id);
+    runtime
+        .run_event_loop(PollEventLoopOptions::default())
+        .await
+        .map_err(js_execution_error)?;
+    result.await.map_err(js_execution_error)?;
+    Ok(())
+}
+
+// Evaluate one snippet against a warm pooled runtime: reset state first, then
+// run through the stashed `runCode`, returning the same `{value, stdout,
+// stderr}` shape as `evaluate`. An execution or deserialize error leaves the
+// runtime reset and reusable for the next checkout.
+#[cfg(not(test))]
+async fn pool_eval(
+    runtime: &mut JsRuntime,
+    code: &str,
+    await_promises: bool,
+    execution_count: u64,
+) -> Result<EvalOutput, RuntimeError> {
+    {
+        let op_state = runtime.op_state();
+        let mut op_state = op_state.borrow_mut();
+        op_state.borrow_mut::<SsrCapture>().html = None;
+        *op_state.borrow_mut::<StdioCapture>() = StdioCapture::default();
+    }
+    runtime
+        .execute_script("<gdansk-pool-reset>", POOL_RESET)
+        .map_err(js_execution_error)?;
+
+    let code_json = deno_core::serde_json::to_string(code).map_err(execution_error)?;
+    let script =
+        format!("globalThis.__gdansk_last_result = globalThis.__gdansk_runCode({code_json});");
+    let resource_name = format!("<gdansk-pool-eval:{execution_count}>");
+    runtime
+        .execute_script(resource_name, script)
+        .map_err(js_execution_error)?;
+    runtime
+        .run_event_loop(PollEventLoopOptions::default())
+        .await
+        .map_err(js_execution_error)?;
+
+    let html = {
+        let op_state = runtime.op_state();
+        let mut op_state = op_state.borrow_mut();
+        op_state.borrow_mut::<SsrCapture>().html.take()
+    };
+
+    let value = if let Some(html) = html {
+        Value::String(html)
+    } else {
+        let output = runtime
+            .execute_script("<gdansk-pool-result>", "globalThis.__gdansk_last_result")
+            .map_err(js_execution_error)?;
+
+        let output = if await_promises && is_promise(runtime, &output) {
+            resolve_promise(runtime, output).await?
+        } else {
+            output
+        };
+
+        read_json_value(runtime, output)?
+    };
+
+    let StdioCapture { stdout, stderr } = {
+        let op_state = runtime.op_state();
+        let mut op_state = op_state.borrow_mut();
+        std::mem::take(op_state.borrow_mut::<StdioCapture>())
+    };
+
+    Ok(EvalOutput {
+        value,
+        stdout,
+        stderr,
+    })
+}
+
+// One checkout handed to a pooled worker, paired with the channel it answers on.
+#[cfg(not(test))]
+struct PoolRequest {
+    code: String,
+    await_promises: bool,
+    execution_count: u64,
+    responder: std::sync::mpsc::Sender<Result<EvalOutput, RuntimeError>>,
+}
+
+// The sender a checkout uses to hand a request to one warm worker. Workers
+// advertise a fresh handle on the `available` channel each time they go idle.
+#[cfg(not(test))]
+type WorkerHandle = std::sync::mpsc::Sender<PoolRequest>;
+
+// Spawn `size` worker threads, each owning a warm `JsRuntime` (which is `!Send`)
+// on its own current-thread Tokio runtime. Workers advertise themselves on the
+// `available` channel; a checkout pops an idle worker's sender, dispatches the
+// request, and the worker re-advertises once it has answered. Returns only after
+// every worker has finished warm-up so the first checkout hits a ready runtime.
+#[cfg(not(test))]
+fn spawn_pool(size: usize) -> Result<std::sync::mpsc::Receiver<WorkerHandle>, RuntimeError> {
+    let size = size.max(1);
+    let (available_tx, available_rx) = std::sync::mpsc::channel::<WorkerHandle>();
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel::<Result<(), RuntimeError>>();
+
+    for _ in 0..size {
+        let available_tx = available_tx.clone();
+        let ready_tx = ready_tx.clone();
+        std::thread::spawn(move || {
+            let tokio_runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(err) => {
+                    let _ = ready_tx.send(Err(execution_error(err)));
+                    return;
+                }
+            };
+
+            tokio_runtime.block_on(async move {
+                let mut runtime = JsRuntime::new(RuntimeOptions {
+                    startup_snapshot: Some(snapshot()),
+                    extensions: vec![gdansk_runtime_ext::init()],
+                    ..Default::default()
+                });
+
+                if let Err(err) = prepare_pool_runtime(&mut runtime).await {
+                    let _ = ready_tx.send(Err(err));
+                    return;
+                }
+                let _ = ready_tx.send(Ok(()));
+
+                let (request_tx, request_rx) = std::sync::mpsc::channel::<PoolRequest>();
+                if available_tx.send(request_tx.clone()).is_err() {
+                    return;
+                }
+
+                while let Ok(request) = request_rx.recv() {
+                    let result =
+                        pool_eval(&mut runtime, &request.code, request.await_promises, request.execution_count)
+                            .await;
+                    let _ = request.responder.send(result);
+                    if available_tx.send(request_tx.clone()).is_err() {
+                        break;
+                    }
+                }
+            });
+        });
+    }
+
+    drop(ready_tx);
+    for _ in 0..size {
+        ready_rx.recv().map_err(execution_error)??;
+    }
+    Ok(available_rx)
+}
+
+// A bounded pool of warm runtimes that amortizes `JsRuntime` construction across
+// `run` calls. Each evaluation is stateless — the runtime is reset on checkout —
+// matching the one-shot `run` semantics while skipping per-call snapshot boot.
+#[cfg(not(test))]
+#[pyclass]
+pub(crate) struct RuntimePool {
+    available: std::sync::Arc<std::sync::Mutex<std::sync::mpsc::Receiver<WorkerHandle>>>,
+    execution_count: AtomicU64,
+}
+
+#[cfg(not(test))]
+#[pymethods]
+impl RuntimePool {
+    #[new]
+    #[pyo3(signature = (size = 2))]
+    fn new(size: usize) -> PyResult<Self> {
+        let available = spawn_pool(size).map_err(map_runtime_error)?;
+        Ok(Self {
+            available: std::sync::Arc::new(std::sync::Mutex::new(available)),
+            execution_count: AtomicU64::new(0),
+        })
+    }
+
+    #[pyo3(signature = (code, await_promises = false))]
+    fn run<'py>(
+        &self,
+        py: Python<'py>,
+        code: &str,
+        await_promises: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let code = code.to_owned();
+        let execution_count = self.execution_count.fetch_add(1, Ordering::SeqCst) + 1;
+        let available = std::sync::Arc::clone(&self.available);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let output = tokio::task::spawn_blocking(move || -> PyResult<EvalOutput> {
+                let worker = available
+                    .lock()
+                    .expect("runtime pool mutex poisoned")
+                    .recv()
+                    .map_err(|err| map_runtime_error(execution_error(err)))?;
+                let (responder, response) = std::sync::mpsc::channel();
+                worker
+                    .send(PoolRequest {
+                        code,
+                        await_promises,
+                        execution_count,
+                        responder,
+                    })
+                    .map_err(|err| map_runtime_error(execution_error(err)))?;
+                response
+                    .recv()
+                    .map_err(|err| map_runtime_error(execution_error(err)))?
+                    .map_err(map_runtime_error)
+            })
+            .await
+            .map_err(|err| map_runtime_error(execution_error(err)))??;
+
+            Python::attach(|py| {
+                let dict = PyDict::new(py);
+                dict.set_item("value", json_to_py(py, &output.value)?)?;
+                dict.set_item("stdout", output.stdout)?;
+                dict.set_item("stderr", output.stderr)?;
+                Ok(dict.into_any().unbind())
+            })
+        })
+    }
+
+    #[getter]
+    fn execution_count(&self) -> u64 {
+        self.execution_count.load(Ordering::SeqCst)
+    }
+}
+
+// Build a `RuntimeError` Python exception carrying the structured JS detail as
+// attributes (`line`, `column`, `source_line`, `script_resource_name`, `stack`)
+// so a failing SSR render points at the offending line of user JavaScript.
+#[cfg(not(test))]
+fn build_execution_pyerr(info: &JsExecutionError) -> PyErr {
+    Python::with_gil(|py| {
+        let err = PyRuntimeError::new_err(info.message.clone());
+        let value = err.value(py);
+        let _ = value.setattr("line", info.line_number);
+        let _ = value.setattr("column", info.start_column);
+        let _ = value.setattr("source_line", info.source_line.clone());
+        let _ = value.setattr("script_resource_name", info.script_resource_name.clone());
+
+        let frames = PyList::empty(py);
+        for frame in &info.frames {
+            let entry = PyDict::new(py);
+            let _ = entry.set_item("function_name", frame.function_name.clone());
+            let _ = entry.set_item("script_name", frame.script_name.clone());
+            let _ = entry.set_item("line", frame.line_number);
+            let _ = entry.set_item("column", frame.column_number);
+            let _ = frames.append(entry);
+        }
+        let _ = value.setattr("stack", frames);
+        err
+    })
 }
 
 #[cfg(not(test))]
 fn map_runtime_error(err: RuntimeError) -> PyErr {
     match err {
-        RuntimeError::Execution(message) => PyRuntimeError::new_err(message),
+        RuntimeError::Execution(info) => build_execution_pyerr(&info),
         RuntimeError::Deserialize(message) => PyValueError::new_err(message),
     }
 }
@@ -178,22 +949,110 @@ fn json_to_py(py: Python<'_>, value: &Value) -> PyResult<Py<PyAny>> {
     Ok(py_value)
 }
 
+// Convert a Python value into `serde_json::Value`, the inverse of `json_to_py`.
+// Integers that overflow `i64` fall back to their `u64` form; anything outside
+// the JSON data model (e.g. a custom object) is a `ValueError`.
+#[cfg(not(test))]
+fn py_to_json(value: &Bound<'_, PyAny>) -> PyResult<Value> {
+    use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString};
+
+    if value.is_none() {
+        return Ok(Value::Null);
+    }
+    if let Ok(boolean) = value.downcast::<PyBool>() {
+        return Ok(Value::Bool(boolean.is_true()));
+    }
+    if let Ok(integer) = value.downcast::<PyInt>() {
+        if let Ok(number) = integer.extract::<i64>() {
+            return Ok(Value::from(number));
+        }
+        if let Ok(number) = integer.extract::<u64>() {
+            return Ok(Value::from(number));
+        }
+        return Err(PyValueError::new_err(
+            "Cannot convert Python integer to JSON: value out of range",
+        ));
+    }
+    if let Ok(float) = value.downcast::<PyFloat>() {
+        let number = float.value();
+        return deno_core::serde_json::Number::from_f64(number)
+            .map(Value::Number)
+            .ok_or_else(|| {
+                PyValueError::new_err("Cannot convert Python float to JSON: value is not finite")
+            });
+    }
+    if let Ok(string) = value.downcast::<PyString>() {
+        return Ok(Value::String(string.to_str()?.to_owned()));
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        let mut items = Vec::with_capacity(list.len());
+        for item in list.iter() {
+            items.push(py_to_json(&item)?);
+        }
+        return Ok(Value::Array(items));
+    }
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut entries = deno_core::serde_json::Map::with_capacity(dict.len());
+        for (key, item) in dict.iter() {
+            let key = key.downcast::<PyString>().map_err(|_| {
+                PyValueError::new_err("Cannot convert Python dict to JSON: keys must be strings")
+            })?;
+            entries.insert(key.to_str()?.to_owned(), py_to_json(&item)?);
+        }
+        return Ok(Value::Object(entries));
+    }
+
+    Err(PyValueError::new_err(
+        "Cannot convert Python object to JSON: unsupported type",
+    ))
+}
+
+// Convert an optional Python mapping of `{ specifier: source }` into the string
+// map the module loader registers, erroring on non-string keys or values.
 #[cfg(not(test))]
-#[pyfunction]
-pub(crate) fn run<'py>(py: Python<'py>, code: &str) -> PyResult<Bound<'py, PyAny>> {
+fn py_to_modules(modules: &Bound<'_, PyAny>) -> PyResult<HashMap<String, String>> {
+    let dict = modules.downcast::<PyDict>().map_err(|_| {
+        PyValueError::new_err("modules must be a mapping of specifier to source string")
+    })?;
+    let mut registered = HashMap::with_capacity(dict.len());
+    for (specifier, source) in dict.iter() {
+        registered.insert(specifier.extract::<String>()?, source.extract::<String>()?);
+    }
+    Ok(registered)
+}
+
+#[cfg(not(test))]
+#[pyfunction(signature = (code, await_promises = false, props = None, modules = None))]
+pub(crate) fn run<'py>(
+    py: Python<'py>,
+    code: &str,
+    await_promises: bool,
+    props: Option<&Bound<'py, PyAny>>,
+    modules: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
     let code = code.to_owned();
+    let props = props.map(py_to_json).transpose()?;
+    let modules = modules.map(py_to_modules).transpose()?;
     pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let value = tokio::task::spawn_blocking(move || -> PyResult<Value> {
+        let output = tokio::task::spawn_blocking(move || -> PyResult<EvalOutput> {
             let runtime = tokio::runtime::Builder::new_current_thread()
                 .enable_all()
                 .build()
                 .map_err(execution_error)
                 .map_err(map_runtime_error)?;
-            runtime.block_on(evaluate(&code)).map_err(map_runtime_error)
+            runtime
+                .block_on(evaluate(&code, await_promises, props, modules))
+                .map_err(map_runtime_error)
         })
         .await
         .map_err(|err| map_runtime_error(execution_error(err)))??;
-        Python::attach(|py| json_to_py(py, &value))
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            dict.set_item("value", json_to_py(py, &output.value)?)?;
+            dict.set_item("stdout", output.stdout)?;
+            dict.set_item("stderr", output.stderr)?;
+            Ok(dict.into_any().unbind())
+        })
     })
 }
 
@@ -204,11 +1063,36 @@ mod tests {
     use super::*;
 
     fn run_value(code: &str) -> Result<Value, RuntimeError> {
+        run_value_with(code, false)
+    }
+
+    fn run_value_with(code: &str, await_promises: bool) -> Result<Value, RuntimeError> {
+        run_eval(code, await_promises).map(|output| output.value)
+    }
+
+    fn run_eval(code: &str, await_promises: bool) -> Result<EvalOutput, RuntimeError> {
+        run_eval_with(code, await_promises, None)
+    }
+
+    fn run_eval_with(
+        code: &str,
+        await_promises: bool,
+        props: Option<Value>,
+    ) -> Result<EvalOutput, RuntimeError> {
+        run_eval_full(code, await_promises, props, None)
+    }
+
+    fn run_eval_full(
+        code: &str,
+        await_promises: bool,
+        props: Option<Value>,
+        modules: Option<HashMap<String, String>>,
+    ) -> Result<EvalOutput, RuntimeError> {
         let runtime = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .map_err(execution_error)?;
-        runtime.block_on(evaluate(code))
+        runtime.block_on(evaluate(code, await_promises, props, modules))
     }
 
     #[test]
@@ -294,6 +1178,20 @@ mod tests {
         assert!(matches!(err, RuntimeError::Deserialize(_)));
     }
 
+    #[test]
+    fn awaits_resolved_promise_in_await_mode() {
+        let result = run_value_with("Promise.resolve(42)", true)
+            .expect("expected awaited promise to resolve");
+        assert_eq!(result, json!(42));
+    }
+
+    #[test]
+    fn rejected_promise_becomes_execution_error_in_await_mode() {
+        let result = run_value_with("Promise.reject(new Error('nope'))", true);
+        let err = result.expect_err("expected rejected promise to fail");
+        assert!(matches!(err, RuntimeError::Execution(_)));
+    }
+
     #[test]
     fn rejects_nan_results() {
         let result = run_value("0/0");
@@ -379,6 +1277,113 @@ mod tests {
         assert!(matches!(err, RuntimeError::Execution(_)));
     }
 
+    #[test]
+    fn loads_registered_modules_by_specifier() {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "app:/value.js".to_string(),
+            "export const value = 7;".to_string(),
+        );
+        let result = run_eval_full(
+            r#"import("app:/value.js").then((m) => m.value)"#,
+            true,
+            None,
+            Some(modules),
+        )
+        .expect("expected registered module to load")
+        .value;
+        assert_eq!(result, json!(7));
+    }
+
+    #[test]
+    fn resolves_relative_specifiers_against_entry_url() {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "file:///gdansk/value.js".to_string(),
+            "export const value = 9;".to_string(),
+        );
+        let result = run_eval_full(
+            r#"import("./value.js").then((m) => m.value)"#,
+            true,
+            None,
+            Some(modules),
+        )
+        .expect("expected relative import to resolve against the entry URL")
+        .value;
+        assert_eq!(result, json!(9));
+    }
+
+    #[test]
+    fn unknown_module_specifier_is_an_execution_error() {
+        let result = run_eval_full(
+            r#"import("app:/missing.js").then((m) => m.value)"#,
+            true,
+            None,
+            Some(HashMap::new()),
+        );
+        let err = result.expect_err("expected unknown module to fail the import");
+        assert!(matches!(err, RuntimeError::Execution(_)));
+    }
+
+    #[test]
+    fn injects_props_into_eval_context() {
+        let props = json!({ "name": "gdansk", "count": 3 });
+        let result = run_eval_with("`${props.name}:${props.count}`", false, Some(props))
+            .expect("expected props to be available")
+            .value;
+        assert_eq!(result, json!("gdansk:3"));
+    }
+
+    #[test]
+    fn injected_props_are_frozen() {
+        let props = json!({ "name": "gdansk" });
+        let result = run_eval_with(
+            r#"(() => { try { props.name = "changed"; } catch (_) {} return props.name; })()"#,
+            false,
+            Some(props),
+        )
+        .expect("expected frozen props to resist mutation")
+        .value;
+        assert_eq!(result, json!("gdansk"));
+    }
+
+    #[test]
+    fn omitting_props_leaves_global_undefined() {
+        let result = run_eval("typeof props", false)
+            .expect("expected evaluation result")
+            .value;
+        assert_eq!(result, json!("undefined"));
+    }
+
+    #[test]
+    fn captures_console_log_on_stdout() {
+        let output = run_eval(r#"console.log("hello", 42); 1"#, false)
+            .expect("expected evaluation result");
+        assert_eq!(output.value, json!(1));
+        assert_eq!(output.stdout, "hello 42\n");
+        assert_eq!(output.stderr, "");
+    }
+
+    #[test]
+    fn captures_console_warn_and_error_on_stderr() {
+        let output = run_eval(r#"console.warn("careful"); console.error("boom"); 1"#, false)
+            .expect("expected evaluation result");
+        assert_eq!(output.value, json!(1));
+        assert_eq!(output.stdout, "");
+        assert_eq!(output.stderr, "careful\nboom\n");
+    }
+
+    #[test]
+    fn console_output_does_not_leak_between_calls() {
+        let first = run_eval(r#"console.log("first"); 1"#, false)
+            .expect("expected first evaluation result");
+        assert_eq!(first.stdout, "first\n");
+
+        let second = run_eval("2 + 2", false).expect("expected second evaluation result");
+        assert_eq!(second.stdout, "");
+        assert_eq!(second.stderr, "");
+    }
+
     #[test]
     fn ssr_output_does_not_leak_between_calls() {
         let first = run_value(r#"Deno.core.ops.op_gdansk_set_html("<div>ok</div>");"#)